@@ -1,7 +1,10 @@
-use crate::model::{ChildActivityHint, EntryKind, FileEntry, TimeBucket};
+use crate::model::{ChildActivityHint, EntryKind, FileEntry, LongMetadata, TimeBucket};
 use crate::util::ignore::load_local_ignore;
+#[cfg(unix)]
+use crate::util::owner::OwnerNames;
 use crate::util::time::classify_bucket;
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::fs::{self, DirEntry, Metadata, ReadDir};
 use std::path::Path;
 use std::time::SystemTime;
@@ -13,6 +16,7 @@ pub enum DotMode {
     None,
 }
 
+#[derive(Clone)]
 pub struct ScanOptions {
     pub dot_mode: DotMode,
     pub use_ignore: bool,
@@ -23,9 +27,12 @@ pub struct ScanOptions {
     pub show_hints: bool,
     pub since: Option<SystemTime>,
     pub since_raw: Option<String>,
+    pub long: bool,
+    pub follow_links: bool,
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(serde::Serialize))]
 pub struct ScanStats {
     pub total_raw_entries: usize,
     pub visible_entries: usize,
@@ -34,6 +41,22 @@ pub struct ScanStats {
     pub filtered_ignored: usize,
     pub filtered_ext: usize,
     pub filtered_type: usize,
+    /// Symlinks that `--follow` could not resolve (broken or looping).
+    pub unresolved_links: usize,
+}
+
+impl ScanStats {
+    /// Add the counts of a nested scan, e.g. a directory `--tree` expanded.
+    pub fn merge(&mut self, other: &ScanStats) {
+        self.total_raw_entries += other.total_raw_entries;
+        self.visible_entries += other.visible_entries;
+        self.skipped_unreadable += other.skipped_unreadable;
+        self.filtered_hidden += other.filtered_hidden;
+        self.filtered_ignored += other.filtered_ignored;
+        self.filtered_ext += other.filtered_ext;
+        self.filtered_type += other.filtered_type;
+        self.unresolved_links += other.unresolved_links;
+    }
 }
 
 pub struct ScanResult {
@@ -58,6 +81,8 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
         .with_context(|| format!("failed to read directory {}", path.display()))?;
     let mut entries = Vec::new();
     let mut stats = ScanStats::default();
+    let owners = opts.long.then(OwnerNames::load);
+    let loop_guard = opts.follow_links.then(|| LoopGuard::for_root(path));
 
     for entry in read_dir {
         let Ok(entry) = entry else {
@@ -67,13 +92,25 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
         stats.total_raw_entries += 1;
 
         let full_path = entry.path();
-        let metadata = match fs::symlink_metadata(&full_path) {
+        let link_metadata = match fs::symlink_metadata(&full_path) {
             Ok(m) => m,
             Err(_) => {
                 stats.skipped_unreadable += 1;
                 continue;
             }
         };
+        let is_link = link_metadata.file_type().is_symlink();
+
+        let metadata = match &loop_guard {
+            Some(guard) if is_link => match guard.follow(&full_path) {
+                Some(target) => target,
+                None => {
+                    stats.unresolved_links += 1;
+                    link_metadata
+                }
+            },
+            _ => link_metadata,
+        };
 
         match should_include_entry(
             &entry,
@@ -103,7 +140,7 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
             FilterDecision::Since => continue,
         }
 
-        let name = entry.file_name().to_string_lossy().to_string();
+        let name = entry.file_name();
         let mtime = match metadata.modified() {
             Ok(t) => t,
             Err(_) => {
@@ -112,18 +149,9 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
             }
         };
 
-        let kind = if metadata.file_type().is_symlink() {
-            EntryKind::Symlink
-        } else if metadata.is_dir() {
-            EntryKind::Dir
-        } else {
-            EntryKind::File
-        };
-
+        let kind = entry_kind(&full_path, &metadata);
         let size = matches!(kind, EntryKind::File).then_some(metadata.len());
-        let symlink_target = matches!(kind, EntryKind::Symlink)
-            .then(|| fs::read_link(&full_path).ok())
-            .flatten();
+        let symlink_target = is_link.then(|| fs::read_link(&full_path).ok()).flatten();
 
         entries.push(FileEntry {
             path: full_path,
@@ -132,6 +160,8 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
             mtime,
             size,
             symlink_target,
+            hash: None,
+            long: long_metadata(&metadata, owners.as_ref()),
         });
         stats.visible_entries += 1;
     }
@@ -145,6 +175,144 @@ pub fn scan_dir(path: &Path, opts: &ScanOptions) -> Result<ScanResult> {
     })
 }
 
+/// One path described the way `scan_dir` lists entries, with the `--long`
+/// details filled in; used by `ftime preview`.
+pub fn stat_entry(path: &Path) -> Result<FileEntry> {
+    let metadata = fs::symlink_metadata(path)
+        .with_context(|| format!("failed to read metadata for {}", path.display()))?;
+    let mtime = metadata
+        .modified()
+        .with_context(|| format!("failed to read mtime for {}", path.display()))?;
+    let kind = entry_kind(path, &metadata);
+    let owners = OwnerNames::load();
+
+    Ok(FileEntry {
+        path: path.to_path_buf(),
+        name: path.file_name().unwrap_or(path.as_os_str()).to_os_string(),
+        kind,
+        mtime,
+        size: matches!(kind, EntryKind::File).then_some(metadata.len()),
+        symlink_target: kind
+            .is_symlink()
+            .then(|| fs::read_link(path).ok())
+            .flatten(),
+        hash: None,
+        long: long_metadata(&metadata, Some(&owners)),
+    })
+}
+
+/// Device and inode of the scan root and its ancestors, used by `--follow` to
+/// refuse links that point back up the tree.
+struct LoopGuard {
+    ancestors: HashSet<(u64, u64)>,
+}
+
+impl LoopGuard {
+    fn for_root(root: &Path) -> Self {
+        let ancestors = fs::canonicalize(root)
+            .map(|root| {
+                root.ancestors()
+                    .filter_map(|dir| fs::metadata(dir).ok())
+                    .filter_map(|metadata| file_identity(&metadata))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { ancestors }
+    }
+
+    /// Target metadata for a symlink, or `None` when it is broken or loops.
+    fn follow(&self, link: &Path) -> Option<Metadata> {
+        let target = fs::metadata(link).ok()?;
+        let loops = file_identity(&target).is_some_and(|id| self.ancestors.contains(&id));
+        if target.is_dir() && loops {
+            return None;
+        }
+        Some(target)
+    }
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Classify an entry from its `symlink_metadata`, resolving symlinks once to
+/// tell directory links and broken links apart.
+fn entry_kind(path: &Path, metadata: &Metadata) -> EntryKind {
+    let file_type = metadata.file_type();
+
+    if file_type.is_symlink() {
+        return match fs::metadata(path) {
+            Ok(target) if target.is_dir() => EntryKind::SymlinkDir,
+            Ok(_) => EntryKind::Symlink,
+            Err(_) => EntryKind::BrokenSymlink,
+        };
+    }
+
+    if file_type.is_dir() {
+        return EntryKind::Dir;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+
+        if file_type.is_fifo() {
+            return EntryKind::Fifo;
+        }
+        if file_type.is_socket() {
+            return EntryKind::Socket;
+        }
+        if file_type.is_block_device() {
+            return EntryKind::BlockDevice;
+        }
+        if file_type.is_char_device() {
+            return EntryKind::CharDevice;
+        }
+    }
+
+    EntryKind::File
+}
+
+#[cfg(unix)]
+fn long_metadata(metadata: &Metadata, owners: Option<&OwnerNames>) -> Option<LongMetadata> {
+    use std::os::unix::fs::MetadataExt;
+
+    let owners = owners?;
+    Some(LongMetadata {
+        mode: metadata.mode(),
+        nlink: metadata.nlink(),
+        inode: metadata.ino(),
+        uid: metadata.uid(),
+        gid: metadata.gid(),
+        owner: owners.user(metadata.uid()),
+        group: owners.group(metadata.gid()),
+    })
+}
+
+#[cfg(not(unix))]
+struct OwnerNames;
+
+#[cfg(not(unix))]
+impl OwnerNames {
+    fn load() -> Self {
+        Self
+    }
+}
+
+#[cfg(not(unix))]
+fn long_metadata(_metadata: &Metadata, _owners: Option<&OwnerNames>) -> Option<LongMetadata> {
+    None
+}
+
 pub fn bucket_heat(bucket: TimeBucket) -> u8 {
     match bucket {
         TimeBucket::History => 0,
@@ -154,6 +322,22 @@ pub fn bucket_heat(bucket: TimeBucket) -> u8 {
     }
 }
 
+/// Scan a directory directly below the root with the root's filters; used by
+/// `--tree` to expand one level. Fails when the directory cannot be read.
+pub fn scan_children(dir_path: &Path, parent_scan_opts: &ScanOptions) -> Result<ScanResult> {
+    let opts = ScanOptions {
+        local_ignore_patterns: if parent_scan_opts.use_ignore {
+            load_local_ignore(dir_path)
+        } else {
+            Vec::new()
+        },
+        show_hints: false,
+        ..parent_scan_opts.clone()
+    };
+
+    scan_dir(dir_path, &opts)
+}
+
 pub fn dir_child_activity_hint(
     dir_path: &Path,
     now: SystemTime,
@@ -181,9 +365,16 @@ pub fn dir_child_activity_hint(
             Err(_) => continue,
         };
 
-        if metadata.file_type().is_symlink() {
+        let metadata = if !metadata.file_type().is_symlink() {
+            metadata
+        } else if parent_scan_opts.follow_links {
+            match fs::metadata(&child_path) {
+                Ok(target) => target,
+                Err(_) => continue,
+            }
+        } else {
             continue;
-        }
+        };
 
         if should_include_entry(
             &entry,
@@ -388,6 +579,16 @@ impl Bucketed {
     pub fn total(&self) -> usize {
         self.active.len() + self.today.len() + self.week.len() + self.history.len()
     }
+
+    /// Buckets in display order, hottest first.
+    pub fn groups(&self) -> [(TimeBucket, &[FileEntry]); 4] {
+        [
+            (TimeBucket::Active, &self.active),
+            (TimeBucket::Today, &self.today),
+            (TimeBucket::ThisWeek, &self.week),
+            (TimeBucket::History, &self.history),
+        ]
+    }
 }
 
 #[cfg(test)]
@@ -411,6 +612,8 @@ mod tests {
             show_hints: true,
             since: None,
             since_raw: None,
+            long: false,
+            follow_links: false,
         }
     }
 
@@ -425,7 +628,7 @@ mod tests {
         let names: Vec<&str> = res
             .entries
             .iter()
-            .map(|entry| entry.name.as_str())
+            .map(|entry| entry.name.to_str().unwrap())
             .collect();
 
         assert!(names.contains(&"visible"));
@@ -476,7 +679,11 @@ mod tests {
         set_file_mtime(&b_path, ft)?;
 
         let res = scan_dir(dir.path(), &scan_options())?;
-        let names: Vec<&str> = res.entries.iter().map(|e| e.name.as_str()).collect();
+        let names: Vec<&str> = res
+            .entries
+            .iter()
+            .map(|e| e.name.to_str().unwrap())
+            .collect();
         assert_eq!(names, vec!["a", "b"]);
         Ok(())
     }
@@ -495,7 +702,11 @@ mod tests {
                 ..scan_options()
             },
         )?;
-        let names: Vec<&str> = res.entries.iter().map(|e| e.name.as_str()).collect();
+        let names: Vec<&str> = res
+            .entries
+            .iter()
+            .map(|e| e.name.to_str().unwrap())
+            .collect();
         assert!(names.contains(&"keep.rs"));
         assert!(names.contains(&"docs"));
         assert!(!names.contains(&"drop.txt"));
@@ -522,16 +733,114 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn long_option_collects_unix_metadata() -> Result<()> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let dir = tempdir()?;
+        let path = dir.path().join("script.sh");
+        File::create(&path)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750))?;
+
+        let plain = scan_dir(dir.path(), &scan_options())?;
+        assert!(plain.entries[0].long.is_none());
+
+        let res = scan_dir(
+            dir.path(),
+            &ScanOptions {
+                long: true,
+                ..scan_options()
+            },
+        )?;
+        let long = res.entries[0].long.as_ref().unwrap();
+        let meta = fs::metadata(&path)?;
+        assert_eq!(long.mode & 0o7777, 0o750);
+        assert_eq!(long.inode, meta.ino());
+        assert_eq!(long.nlink, 1);
+        assert_eq!(long.uid, meta.uid());
+        assert!(!long.owner.is_empty());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_distinguishes_special_files_and_symlink_targets() -> Result<()> {
+        use std::os::unix::fs::symlink;
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempdir()?;
+        fs::create_dir(dir.path().join("real_dir"))?;
+        File::create(dir.path().join("real_file"))?;
+        symlink(dir.path().join("real_dir"), dir.path().join("dir_link"))?;
+        symlink(dir.path().join("real_file"), dir.path().join("file_link"))?;
+        symlink(dir.path().join("missing"), dir.path().join("dangling"))?;
+        let _listener = UnixListener::bind(dir.path().join("app.sock"))?;
+
+        let res = scan_dir(dir.path(), &scan_options())?;
+        let kind_of = |name: &str| {
+            res.entries
+                .iter()
+                .find(|entry| entry.name == name)
+                .map(|entry| (entry.kind, entry.size))
+                .unwrap()
+        };
+
+        assert_eq!(kind_of("dir_link"), (EntryKind::SymlinkDir, None));
+        assert_eq!(kind_of("file_link"), (EntryKind::Symlink, None));
+        assert_eq!(kind_of("dangling"), (EntryKind::BrokenSymlink, None));
+        assert_eq!(kind_of("app.sock"), (EntryKind::Socket, None));
+        assert_eq!(kind_of("real_file"), (EntryKind::File, Some(0)));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follow_links_uses_target_metadata_and_refuses_loops() -> Result<()> {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempdir()?;
+        let target = dir.path().join("payload.bin");
+        fs::write(&target, b"12345")?;
+        let old = SystemTime::now() - Duration::from_secs(3 * 24 * 3600);
+        set_file_mtime(&target, FileTime::from_system_time(old))?;
+        symlink(&target, dir.path().join("payload_link"))?;
+        symlink(dir.path(), dir.path().join("self_link"))?;
+        symlink(dir.path().join("missing"), dir.path().join("dangling"))?;
+
+        let res = scan_dir(
+            dir.path(),
+            &ScanOptions {
+                follow_links: true,
+                ..scan_options()
+            },
+        )?;
+        let entry = |name: &str| res.entries.iter().find(|e| e.name == name).unwrap();
+
+        let followed = entry("payload_link");
+        assert_eq!(followed.kind, EntryKind::File);
+        assert_eq!(followed.size, Some(5));
+        assert_eq!(followed.mtime, fs::metadata(&target)?.modified()?);
+        assert_eq!(followed.symlink_target.as_deref(), Some(target.as_path()));
+
+        assert_eq!(entry("self_link").kind, EntryKind::SymlinkDir);
+        assert_eq!(entry("dangling").kind, EntryKind::BrokenSymlink);
+        assert_eq!(res.stats.unresolved_links, 2);
+        Ok(())
+    }
+
     #[test]
     fn bucketize_groups_correctly() {
         let now = SystemTime::now();
         let mk = |delta_secs: u64| FileEntry {
             path: PathBuf::from("x"),
-            name: "x".to_string(),
+            name: "x".into(),
             kind: EntryKind::File,
             mtime: now - Duration::from_secs(delta_secs),
             size: Some(0),
             symlink_target: None,
+            hash: None,
+            long: None,
         };
         let entries = vec![mk(10), mk(4000), mk(2 * 24 * 3600), mk(8 * 24 * 3600)];
         let b = bucketize(&entries, now);
@@ -4,32 +4,96 @@ mod util;
 mod view;
 
 use anyhow::{Context, Result, bail};
-use clap::Parser;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 use engine::{DotMode, ScanOptions, bucketize, scan_dir};
+use model::{EntryKind, TimeBucket};
 use std::env;
+use std::ffi::OsString;
+use std::io::{self, Write};
 use std::path::PathBuf;
 use std::process;
+use std::time::SystemTime;
+use util::exec::CommandTemplate;
+use util::hash::{DEFAULT_HASH_MAX_SIZE, HashAlgorithm, hash_entries, parse_size};
 use util::ignore::{load_ignore_patterns, load_local_ignore};
-use util::time::parse_since;
-use view::tty::ColorMode;
+use util::shell::InitShell;
+use util::time::{parse_since, parse_time_bound};
+use view::delimited::{DEFAULT_COLUMNS, DelimitedOptions, Delimiter, parse_columns};
+use view::hyperlink::{HyperlinkMode, Linker, should_hyperlink};
+use view::icon::{DEFAULT_ICON_SET, IconSet};
+use view::limit::{Limits, parse_limits};
+use view::picker::PickerOptions;
+use view::quote::QuotingStyle;
+use view::summary::Summary;
+use view::template::Template;
+use view::theme::Theme;
+use view::timeline::{Timeline, TimelineBin};
+use view::tty::{ColorMode, TruncateMode};
 
 #[derive(Parser, Debug)]
 #[command(
     name = "ftime",
     version,
     about = "files by time: a read-only File Time CLI",
-    after_help = "Default output is the human bucket view. Use --plain or --json for script-friendly output."
+    after_help = "Default output is the human bucket view. Use --plain or --json for script-friendly output.",
+    args_conflicts_with_subcommands = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Emit plain TSV output
     #[arg(long = "plain")]
     plain: bool,
 
+    /// Write non-UTF-8 names in --plain output as raw bytes instead of \xNN escapes
+    #[arg(long = "raw-bytes")]
+    raw_bytes: bool,
+
     /// Emit JSON Lines output
     #[cfg(feature = "json")]
     #[arg(long = "json")]
     json: bool,
 
+    /// JSON Lines schema version (2 adds a leading schema_version field)
+    #[cfg(feature = "json")]
+    #[arg(long = "json-version", value_name = "VERSION", value_parser = json_version_parser())]
+    json_version: Option<u8>,
+
+    /// Emit one JSON document with root, filters, stats, and bucketed entries
+    #[cfg(feature = "json")]
+    #[arg(long = "json-doc")]
+    json_doc: bool,
+
+    /// Emit one line per entry from a template such as '{bucket}\t{path}\t{size}'
+    #[arg(long = "format", value_name = "TEMPLATE")]
+    format: Option<String>,
+
+    /// Emit CSV (RFC 4180) with a header row
+    #[arg(long = "csv")]
+    csv: bool,
+
+    /// Emit escaped TSV with a header row
+    #[arg(long = "tsv")]
+    tsv: bool,
+
+    /// Comma-separated columns for --csv/--tsv (same names as --format placeholders)
+    #[arg(long = "columns", value_name = "LIST")]
+    columns: Option<String>,
+
+    /// Omit the header row from --csv/--tsv output
+    #[arg(long = "no-header")]
+    no_header: bool,
+
+    /// Emit NUL-separated paths for xargs -0 (with --picker: NUL-terminated picker records)
+    #[arg(long = "print0")]
+    print0: bool,
+
+    /// Emit fzf-friendly lines: absolute path, then aligned, colored time, type, and name (tab-separated)
+    #[arg(long = "picker")]
+    picker: bool,
+
     /// Show hidden files and hidden directories
     #[arg(short = 'a', long = "all")]
     all: bool,
@@ -46,6 +110,10 @@ struct Cli {
     #[arg(long = "ext")]
     ext: Option<String>,
 
+    /// Follow symlinks and report their targets' type, size, and mtime
+    #[arg(short = 'L', long = "follow")]
+    follow: bool,
+
     /// Only show regular files
     #[arg(long = "files-only")]
     files_only: bool,
@@ -54,25 +122,125 @@ struct Cli {
     #[arg(long = "since", value_name = "SINCE")]
     since: Option<String>,
 
+    /// Add a content digest to plain and JSON output for regular files
+    #[arg(long = "hash", value_enum, value_name = "ALGORITHM")]
+    hash: Option<HashAlgorithm>,
+
+    /// Skip hashing files larger than this size (default: 64M)
+    #[arg(long = "hash-max-size", value_name = "SIZE")]
+    hash_max_size: Option<String>,
+
     /// Expand the History bucket
     #[arg(long = "all-history")]
     all_history: bool,
 
+    /// Keep the newest N entries, and/or set bucket previews (e.g. 50 or active=50,history=0)
+    #[arg(short = 'n', long = "limit", value_name = "LIMIT")]
+    limit: Option<String>,
+
     /// Emit absolute local timestamps with UTC offset instead of relative time
     #[arg(short = 'A', long = "absolute")]
     absolute_time: bool,
 
+    /// Show Unix mode, link count, owner, group, and inode columns
+    #[arg(short = 'l', long = "long")]
+    long: bool,
+
     /// Show directory child activity hints
     #[arg(long = "hints")]
     hints: bool,
 
+    /// How names are quoted in human and --plain output (human default: escape, plain default: literal)
+    #[arg(long = "quoting-style", value_enum, value_name = "STYLE")]
+    quoting_style: Option<QuotingStyle>,
+
+    /// Run CMD for each matching entry (all of them, not only the human view's bucket previews) in parallel instead of listing; {} path, {/} name, {//} dir, {.} and {/.} without extension; end with ';' to pass more flags
+    #[arg(
+        short = 'x',
+        long = "exec",
+        value_name = "CMD",
+        num_args = 1..,
+        allow_hyphen_values = true,
+        value_terminator = ";"
+    )]
+    exec: Option<Vec<OsString>>,
+
+    /// Run CMD with every matching entry as arguments instead of listing, in several runs if they exceed the command-line limit (same placeholders as --exec)
+    #[arg(
+        short = 'X',
+        long = "exec-batch",
+        value_name = "CMD",
+        num_args = 1..,
+        allow_hyphen_values = true,
+        value_terminator = ";"
+    )]
+    exec_batch: Option<Vec<OsString>>,
+
+    /// Lay out each bucket's names in ls-style columns
+    #[arg(long = "grid")]
+    grid: bool,
+
+    /// Show recent entries as a directory tree, expanding directories one level
+    #[arg(long = "tree")]
+    tree: bool,
+
+    /// Collapse --tree branches with nothing newer than this (default: 7d)
+    #[arg(long = "tree-collapse", value_name = "SINCE")]
+    tree_collapse: Option<String>,
+
+    /// Chart modification counts per hour or day instead of listing entries (default: day)
+    #[arg(
+        long = "timeline",
+        value_enum,
+        value_name = "BIN",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "day"
+    )]
+    timeline: Option<TimelineBin>,
+
+    /// Draw a calendar heatmap of daily activity over the last WEEKS weeks (default: 12)
+    #[arg(
+        long = "heatmap",
+        value_name = "WEEKS",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "12",
+        value_parser = clap::value_parser!(u16).range(1..=53)
+    )]
+    heatmap: Option<u16>,
+
+    /// Aggregate counts and bytes per bucket, extension, and top-level directory
+    #[arg(long = "summary")]
+    summary: bool,
+
+    /// Color theme for human views: default, mono, solarized, high-contrast, or a theme file
+    #[arg(long = "theme", value_name = "THEME")]
+    theme: Option<String>,
+
+    /// How over-long names are shortened in the human view
+    #[arg(long = "truncate", value_enum, value_name = "MODE")]
+    truncate: Option<TruncateMode>,
+
     /// Color handling for human output
     #[arg(long = "color", value_enum, default_value_t = ColorMode::Auto)]
     color: ColorMode,
 
-    /// Show Nerd Font icons (opt-in)
-    #[arg(short = 'I', long = "icons")]
-    use_icons: bool,
+    /// Make names clickable file:// links (OSC 8) in the human view [default: off]
+    #[arg(long = "hyperlink", value_enum, value_name = "WHEN")]
+    hyperlink: Option<HyperlinkMode>,
+
+    /// Show bucket and per-entry icons: nerd (Nerd Font builds), emoji, or ascii
+    #[arg(
+        short = 'I',
+        long = "icons",
+        value_enum,
+        value_name = "SET",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = DEFAULT_ICON_SET
+    )]
+    icons: Option<IconSet>,
 
     /// Check whether a newer published release is available
     #[arg(long = "check-update")]
@@ -86,6 +254,53 @@ struct Cli {
     path: Option<PathBuf>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the JSON Schema for --json output
+    #[cfg(feature = "json")]
+    Schema {
+        /// Emit the schema as JSON (the only supported format)
+        #[arg(long = "json")]
+        json: bool,
+
+        /// JSON Lines schema version to describe
+        #[arg(long = "json-version", value_name = "VERSION", value_parser = json_version_parser())]
+        json_version: Option<u8>,
+    },
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Print the ftime(1) man page (roff) generated from the flags and docs/CLI.md
+    Man,
+
+    /// Print a metadata card for PATH, sized for a fuzzy finder's preview window
+    Preview {
+        /// File or directory to describe
+        path: PathBuf,
+
+        /// Color handling (auto keeps color unless NO_COLOR is set)
+        #[arg(long = "color", value_enum, default_value_t = ColorMode::Auto)]
+        color: ColorMode,
+    },
+
+    /// Print shell widgets: Ctrl-T recent-file picker (needs fzf) and `fcd`
+    ShellInit {
+        /// Shell to print the widgets for
+        #[arg(value_enum)]
+        shell: InitShell,
+    },
+}
+
+#[cfg(feature = "json")]
+fn json_version_parser() -> clap::builder::RangedI64ValueParser<u8> {
+    clap::value_parser!(u8).range(1..=i64::from(view::json::LATEST_JSON_LINES_VERSION))
+}
+
 fn main() {
     if let Err(err) = run() {
         eprintln!("{err}");
@@ -96,6 +311,29 @@ fn main() {
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
+    match cli.command {
+        #[cfg(feature = "json")]
+        Some(Command::Schema { json_version, .. }) => {
+            return view::json::render_schema(json_version.unwrap_or(1));
+        }
+        Some(Command::Completions { shell }) => {
+            return util::shell::print_completions(shell, &mut Cli::command());
+        }
+        Some(Command::Man) => {
+            io::stdout().write_all(&util::man::render(Cli::command())?)?;
+            return Ok(());
+        }
+        Some(Command::Preview { ref path, color }) => {
+            view::theme::install(load_theme(&cli)?);
+            return preview(path, color);
+        }
+        Some(Command::ShellInit { shell }) => {
+            print!("{}", util::shell::init_script(shell));
+            return Ok(());
+        }
+        None => {}
+    }
+
     if cli.self_update || cli.check_update {
         if cli.self_update && cli.check_update {
             bail!("--self-update and --check-update cannot be combined");
@@ -108,7 +346,7 @@ fn run() -> Result<()> {
         }
 
         #[cfg(feature = "json")]
-        if cli.json {
+        if cli.json || cli.json_doc || cli.json_version.is_some() {
             bail!("{update_flag} cannot be combined with scan options or PATH");
         }
 
@@ -124,6 +362,31 @@ fn run() -> Result<()> {
     }
 
     validate_output_flags(&cli)?;
+    let template = cli.format.as_deref().map(Template::parse).transpose()?;
+    let exec = match (&cli.exec, &cli.exec_batch) {
+        (Some(args), _) => Some(CommandTemplate::parse("--exec", args)?),
+        (None, Some(args)) => Some(CommandTemplate::parse("--exec-batch", args)?),
+        (None, None) => None,
+    };
+    if let Some(template) = &template {
+        validate_template_fields(&cli, template)?;
+    }
+    let columns = match cli.columns.as_deref() {
+        Some(list) => parse_columns(list)?,
+        None => DEFAULT_COLUMNS.to_vec(),
+    };
+    validate_columns(&cli, &columns)?;
+    let limits = cli
+        .limit
+        .as_deref()
+        .map(parse_limits)
+        .transpose()?
+        .unwrap_or_default();
+    validate_limits(&cli, &limits)?;
+    let bucketed = bucketed_view(&cli);
+    if matches!(output_mode_flags(&cli).as_slice(), [] | ["--picker"]) {
+        view::theme::install(load_theme(&cli)?);
+    }
 
     let path = match cli.path {
         Some(p) => p,
@@ -174,19 +437,155 @@ fn run() -> Result<()> {
         show_hints: cli.hints,
         since,
         since_raw: cli.since.clone(),
+        long: cli.long,
+        follow_links: cli.follow,
     };
 
-    let scan = scan_dir(&path, &scan_opts)?;
+    let hash_max_size = cli
+        .hash_max_size
+        .as_deref()
+        .map(parse_size)
+        .transpose()?
+        .unwrap_or(DEFAULT_HASH_MAX_SIZE);
+
+    let mut scan = scan_dir(&path, &scan_opts)?;
+    if let Some(limit) = limits.global
+        && !bucketed
+    {
+        scan.entries.truncate(limit);
+    }
+    if let Some(algorithm) = cli.hash {
+        hash_entries(&mut scan.entries, algorithm, hash_max_size);
+    }
+
+    if let Some(command) = &exec {
+        return if cli.exec_batch.is_some() {
+            util::exec::run_batch(command, &scan.entries)
+        } else {
+            util::exec::run_each(command, &scan.entries)
+        };
+    }
+
+    if cli.summary {
+        let summary = Summary::build(
+            &bucketize(&scan.entries, scan.now),
+            &scan.stats,
+            &scan_opts,
+            scan.now,
+        );
+        #[cfg(feature = "json")]
+        if cli.json {
+            return view::json::render_summary(&summary);
+        }
+        return view::summary::render(&summary, cli.color);
+    }
+
+    if let Some(weeks) = cli.heatmap {
+        return view::tty::render_heatmap(&scan.entries, scan.now, usize::from(weeks), cli.color);
+    }
+
+    if let Some(bin) = cli.timeline {
+        let timeline = Timeline::build(&scan.entries, scan.now, bin, bin.default_span());
+        #[cfg(feature = "json")]
+        if cli.json {
+            return view::json::render_timeline(&timeline);
+        }
+        return view::timeline::render(&timeline, scan.now, cli.color);
+    }
 
     #[cfg(feature = "json")]
     if cli.json {
-        return view::json::render(&scan.entries, scan.now, &path);
+        return view::json::render(
+            &scan.entries,
+            scan.now,
+            &path,
+            cli.json_version.unwrap_or(1),
+        );
+    }
+
+    #[cfg(feature = "json")]
+    if cli.json_doc {
+        return view::json::render_document(
+            &bucketize(&scan.entries, scan.now),
+            &scan.stats,
+            &scan_opts,
+            scan.now,
+            &path,
+        );
+    }
+
+    if let Some(template) = &template {
+        return view::template::render(&scan.entries, scan.now, &path, template);
+    }
+
+    if cli.csv || cli.tsv {
+        return view::delimited::render(
+            &scan.entries,
+            scan.now,
+            &path,
+            DelimitedOptions {
+                delimiter: if cli.csv {
+                    Delimiter::Csv
+                } else {
+                    Delimiter::Tsv
+                },
+                columns: &columns,
+                header: !cli.no_header,
+            },
+        );
+    }
+
+    if cli.picker {
+        return view::picker::render(
+            &scan.entries,
+            scan.now,
+            &path,
+            PickerOptions {
+                use_absolute: cli.absolute_time,
+                color_mode: cli.color,
+                null_terminated: cli.print0,
+            },
+        );
+    }
+
+    if cli.print0 {
+        return view::delimited::render_print0(&scan.entries, &path);
     }
 
     if cli.plain {
-        return view::text::render(&scan.entries, scan.now, &path, cli.absolute_time);
+        return view::text::render(
+            &scan.entries,
+            scan.now,
+            &path,
+            cli.absolute_time,
+            cli.hash.is_some(),
+            cli.raw_bytes,
+            cli.quoting_style.unwrap_or(QuotingStyle::Literal),
+        );
     }
 
+    if cli.tree {
+        let collapse_before = parse_time_bound(
+            "--tree-collapse",
+            cli.tree_collapse.as_deref().unwrap_or("7d"),
+            now,
+        )?;
+        return view::tree::render(
+            &scan.entries,
+            &scan.stats,
+            view::tree::TreeOptions {
+                now: scan.now,
+                base: &path,
+                collapse_before,
+                quoting: cli.quoting_style.unwrap_or(QuotingStyle::Escape),
+                color_mode: cli.color,
+                scan_opts: &scan_opts,
+            },
+        );
+    }
+
+    // Opt-in, like `ls` and `fd`: without the flag no OSC 8 escapes are written.
+    let linker = should_hyperlink(cli.hyperlink.unwrap_or(HyperlinkMode::Never)).then(Linker::new);
     let bucketed = bucketize(&scan.entries, scan.now);
     view::tty::render(
         &bucketed,
@@ -194,10 +593,17 @@ fn run() -> Result<()> {
         view::tty::RenderOptions {
             now: scan.now,
             base: &path,
-            show_all_history: cli.all_history,
-            use_icons: cli.use_icons,
+            limits: limits.preview(cli.all_history),
+            limit: limits.global,
+            icons: cli.icons,
+            hyperlinks: linker.as_ref(),
             use_absolute: cli.absolute_time,
+            long: cli.long,
             color_mode: cli.color,
+            quoting: cli.quoting_style.unwrap_or(QuotingStyle::Escape),
+            truncate: cli.truncate.unwrap_or(TruncateMode::Suffix),
+            grid: cli.grid,
+            width: view::tty::terminal_width(),
             scan_opts: &scan_opts,
         },
     )?;
@@ -214,25 +620,379 @@ fn validate_output_flags(cli: &Cli) -> Result<()> {
         bail!("-a and --hide-dots cannot be combined");
     }
 
+    if let [first, second, ..] = output_mode_flags(cli).as_slice() {
+        bail!("{first} and {second} cannot be combined");
+    }
+
     #[cfg(feature = "json")]
     if cli.json
         && (cli.absolute_time
             || cli.all_history
             || cli.hints
-            || cli.use_icons
+            || cli.icons.is_some()
             || cli.color != ColorMode::Auto)
     {
         bail!("--json cannot be combined with human-only flags");
     }
 
-    if cli.plain && (cli.all_history || cli.hints || cli.use_icons || cli.color != ColorMode::Auto)
+    #[cfg(feature = "json")]
+    if cli.json_version.is_some() && !cli.json {
+        bail!("--json-version requires --json");
+    }
+
+    #[cfg(feature = "json")]
+    if cli.json_doc
+        && (cli.absolute_time
+            || cli.all_history
+            || cli.hints
+            || cli.icons.is_some()
+            || cli.color != ColorMode::Auto)
+    {
+        bail!("--json-doc cannot be combined with human-only flags");
+    }
+
+    if cli.plain
+        && (cli.all_history || cli.hints || cli.icons.is_some() || cli.color != ColorMode::Auto)
     {
         bail!("--plain cannot be combined with human-only flags");
     }
 
+    if cli.picker && (cli.all_history || cli.hints || cli.icons.is_some()) {
+        bail!("--picker cannot be combined with --all-history, --hints, or --icons");
+    }
+
+    for (enabled, flag) in [
+        (cli.format.is_some(), "--format"),
+        (cli.csv, "--csv"),
+        (cli.tsv, "--tsv"),
+        (cli.print0 && !cli.picker, "--print0"),
+    ] {
+        if enabled
+            && (cli.absolute_time
+                || cli.all_history
+                || cli.hints
+                || cli.icons.is_some()
+                || cli.color != ColorMode::Auto)
+        {
+            bail!("{flag} cannot be combined with human-only flags");
+        }
+    }
+
+    if (cli.columns.is_some() || cli.no_header) && !(cli.csv || cli.tsv) {
+        bail!("--columns and --no-header require --csv or --tsv");
+    }
+
+    let machine_output = cli.plain
+        || json_output(cli)
+        || json_doc_output(cli)
+        || cli.format.is_some()
+        || cli.csv
+        || cli.tsv;
+
+    if cli.hash.is_some() && !machine_output {
+        bail!("--hash requires --plain, --json, --json-doc, --format, --csv, or --tsv");
+    }
+
+    if cli.raw_bytes && !cli.plain {
+        bail!("--raw-bytes requires --plain");
+    }
+
+    if cli.quoting_style.is_some() && !matches!(output_mode_flags(cli).as_slice(), [] | ["--plain"])
+    {
+        bail!("--quoting-style only applies to the human view and --plain");
+    }
+
+    if cli.theme.is_some() && !matches!(output_mode_flags(cli).as_slice(), [] | ["--picker"]) {
+        bail!("--theme only applies to human views and --picker");
+    }
+
+    if let Some(flag) = exec_flag(cli) {
+        if cli.exec.is_some() && cli.exec_batch.is_some() {
+            bail!("--exec and --exec-batch cannot be combined");
+        }
+        if let Some(first) = output_mode_flags(cli).first() {
+            bail!("{flag} cannot be combined with {first}");
+        }
+        if cli.grid
+            || cli.tree
+            || cli.timeline.is_some()
+            || cli.heatmap.is_some()
+            || cli.summary
+            || cli.long
+            || cli.hints
+        {
+            bail!(
+                "{flag} cannot be combined with --grid, --tree, --timeline, --heatmap, --summary, --long, or --hints"
+            );
+        }
+    }
+
+    if cli.hyperlink.is_some() && !bucketed_view(cli) {
+        bail!("--hyperlink only applies to the bucketed human view");
+    }
+
+    if cli.truncate.is_some() && !output_mode_flags(cli).is_empty() {
+        bail!("--truncate only applies to the human view");
+    }
+
+    if cli.grid && !output_mode_flags(cli).is_empty() {
+        bail!("--grid only applies to the human view");
+    }
+
+    if cli.grid && (cli.long || cli.hints) {
+        bail!("--grid cannot be combined with --long or --hints");
+    }
+
+    if cli.tree && !output_mode_flags(cli).is_empty() {
+        bail!("--tree only applies to the human view");
+    }
+
+    if cli.tree && (cli.grid || cli.long || cli.hints || cli.all_history || cli.absolute_time) {
+        bail!(
+            "--tree cannot be combined with --grid, --long, --hints, --all-history, or --absolute"
+        );
+    }
+
+    if cli.tree_collapse.is_some() && !cli.tree {
+        bail!("--tree-collapse requires --tree");
+    }
+
+    if cli.timeline.is_some() && !matches!(output_mode_flags(cli).as_slice(), [] | ["--json"]) {
+        bail!("--timeline only applies to the human view and --json");
+    }
+
+    if cli.timeline.is_some()
+        && (cli.grid || cli.tree || cli.long || cli.hints || cli.all_history || cli.hash.is_some())
+    {
+        bail!(
+            "--timeline cannot be combined with --grid, --tree, --long, --hints, --all-history, or --hash"
+        );
+    }
+
+    if cli.heatmap.is_some() && !output_mode_flags(cli).is_empty() {
+        bail!("--heatmap only applies to the human view");
+    }
+
+    if cli.heatmap.is_some()
+        && (cli.grid
+            || cli.tree
+            || cli.timeline.is_some()
+            || cli.long
+            || cli.hints
+            || cli.all_history)
+    {
+        bail!(
+            "--heatmap cannot be combined with --grid, --tree, --timeline, --long, --hints, or --all-history"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    if cli.timeline.is_some() && cli.json_version.is_some() {
+        bail!("--json-version cannot be combined with --timeline");
+    }
+
+    if cli.summary && !matches!(output_mode_flags(cli).as_slice(), [] | ["--json"]) {
+        bail!("--summary only applies to the human view and --json");
+    }
+
+    if cli.summary
+        && (cli.grid
+            || cli.tree
+            || cli.timeline.is_some()
+            || cli.heatmap.is_some()
+            || cli.long
+            || cli.hints
+            || cli.hash.is_some())
+    {
+        bail!(
+            "--summary cannot be combined with --grid, --tree, --timeline, --heatmap, --long, --hints, or --hash"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    if cli.summary && cli.json_version.is_some() {
+        bail!("--json-version cannot be combined with --summary");
+    }
+
+    if cli.raw_bytes
+        && cli
+            .quoting_style
+            .is_some_and(|style| style != QuotingStyle::Literal)
+    {
+        bail!("--raw-bytes cannot be combined with --quoting-style other than literal");
+    }
+
+    if cli.hash_max_size.is_some() && cli.hash.is_none() {
+        bail!("--hash-max-size requires --hash");
+    }
+
+    if cli.plain && cli.long {
+        bail!("--plain cannot be combined with --long");
+    }
+
+    #[cfg(not(unix))]
+    if cli.long {
+        bail!("--long is only supported on Unix");
+    }
+
+    Ok(())
+}
+
+/// Script-facing output modes in the order they are reported when combined.
+fn output_mode_flags(cli: &Cli) -> Vec<&'static str> {
+    [
+        (cli.plain, "--plain"),
+        (json_output(cli), "--json"),
+        (json_doc_output(cli), "--json-doc"),
+        (cli.format.is_some(), "--format"),
+        (cli.csv, "--csv"),
+        (cli.tsv, "--tsv"),
+        // `--picker --print0` is the picker with NUL-terminated records.
+        (cli.print0 && !cli.picker, "--print0"),
+        (cli.picker, "--picker"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, flag)| enabled.then_some(flag))
+    .collect()
+}
+
+/// Flag that must be present for a `--format` placeholder or `--columns` entry.
+fn field_requirement(cli: &Cli, field: &str) -> Option<&'static str> {
+    match field {
+        "hash" if cli.hash.is_none() => Some("--hash"),
+        "mode" | "nlink" | "inode" | "owner" | "group" if !cli.long => Some("--long"),
+        _ => None,
+    }
+}
+
+fn validate_template_fields(cli: &Cli, template: &Template) -> Result<()> {
+    for field in view::template::PLACEHOLDERS {
+        if template.uses(field)
+            && let Some(flag) = field_requirement(cli, field)
+        {
+            bail!("--format placeholder {{{field}}} requires {flag}");
+        }
+    }
+
     Ok(())
 }
 
+/// `ftime preview`: the card for one path, listing a directory's newest
+/// children with the default filters.
+fn preview(path: &std::path::Path, color: ColorMode) -> Result<()> {
+    let entry = engine::stat_entry(path)?;
+    let children = matches!(entry.kind, EntryKind::Dir | EntryKind::SymlinkDir)
+        .then(|| {
+            scan_dir(
+                path,
+                &ScanOptions {
+                    dot_mode: DotMode::Default,
+                    use_ignore: true,
+                    ignore_patterns: load_ignore_patterns(),
+                    local_ignore_patterns: load_local_ignore(path),
+                    ext_filter: None,
+                    files_only: false,
+                    show_hints: false,
+                    since: None,
+                    since_raw: None,
+                    long: false,
+                    follow_links: false,
+                },
+            )
+        })
+        .transpose()?;
+    view::picker::render_preview(&entry, children.as_ref(), SystemTime::now(), color)
+}
+
+/// `--theme`, else the `FTIME_THEME` environment variable, else `default`.
+fn load_theme(cli: &Cli) -> Result<Theme> {
+    if let Some(spec) = &cli.theme {
+        return Theme::load("--theme", spec);
+    }
+
+    match env::var("FTIME_THEME") {
+        Ok(spec) if !spec.trim().is_empty() => Theme::load("FTIME_THEME", spec.trim()),
+        _ => Ok(Theme::default()),
+    }
+}
+
+fn validate_limits(cli: &Cli, limits: &Limits) -> Result<()> {
+    if limits.buckets.is_empty() {
+        return Ok(());
+    }
+
+    if !bucketed_view(cli) {
+        bail!("per-bucket --limit values only apply to the bucketed human view");
+    }
+
+    if cli.all_history
+        && limits
+            .buckets
+            .iter()
+            .any(|(bucket, _)| *bucket == TimeBucket::History)
+    {
+        bail!("--all-history cannot be combined with --limit history=N");
+    }
+
+    Ok(())
+}
+
+/// The default bucketed listing (rows or `--grid`), as opposed to machine
+/// output or the alternative human views.
+fn bucketed_view(cli: &Cli) -> bool {
+    output_mode_flags(cli).is_empty()
+        && exec_flag(cli).is_none()
+        && !cli.tree
+        && cli.timeline.is_none()
+        && cli.heatmap.is_none()
+        && !cli.summary
+}
+
+/// `--exec` or `--exec-batch`, which replace the listing with running a command.
+fn exec_flag(cli: &Cli) -> Option<&'static str> {
+    if cli.exec.is_some() {
+        Some("--exec")
+    } else if cli.exec_batch.is_some() {
+        Some("--exec-batch")
+    } else {
+        None
+    }
+}
+
+fn validate_columns(cli: &Cli, columns: &[&str]) -> Result<()> {
+    if !(cli.csv || cli.tsv) {
+        return Ok(());
+    }
+
+    for field in columns {
+        if let Some(flag) = field_requirement(cli, field) {
+            bail!("--columns {field} requires {flag}");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+fn json_output(cli: &Cli) -> bool {
+    cli.json
+}
+
+#[cfg(not(feature = "json"))]
+fn json_output(_cli: &Cli) -> bool {
+    false
+}
+
+#[cfg(feature = "json")]
+fn json_doc_output(cli: &Cli) -> bool {
+    cli.json_doc
+}
+
+#[cfg(not(feature = "json"))]
+fn json_doc_output(_cli: &Cli) -> bool {
+    false
+}
+
 fn update_flag_name(cli: &Cli) -> &'static str {
     if cli.self_update {
         "--self-update"
@@ -244,17 +1004,42 @@ fn update_flag_name(cli: &Cli) -> &'static str {
 fn has_scan_options(cli: &Cli) -> bool {
     cli.path.is_some()
         || cli.plain
+        || cli.raw_bytes
+        || cli.format.is_some()
+        || cli.csv
+        || cli.tsv
+        || cli.columns.is_some()
+        || cli.no_header
+        || cli.print0
+        || cli.picker
         || cli.no_ignore
         || cli.all
         || cli.hide_dots
         || cli.ext.is_some()
         || cli.files_only
+        || cli.follow
         || cli.since.is_some()
+        || cli.hash.is_some()
+        || cli.hash_max_size.is_some()
         || cli.all_history
+        || cli.limit.is_some()
+        || cli.theme.is_some()
         || cli.hints
-        || cli.use_icons
+        || cli.long
+        || cli.icons.is_some()
+        || cli.quoting_style.is_some()
+        || cli.truncate.is_some()
+        || cli.grid
+        || cli.tree
+        || cli.tree_collapse.is_some()
+        || cli.timeline.is_some()
+        || cli.heatmap.is_some()
+        || cli.summary
         || cli.absolute_time
         || cli.color != ColorMode::Auto
+        || cli.hyperlink.is_some()
+        || cli.exec.is_some()
+        || cli.exec_batch.is_some()
 }
 
 #[cfg(test)]
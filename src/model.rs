@@ -1,21 +1,66 @@
-use std::path::PathBuf;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EntryKind {
     File,
     Dir,
+    /// Symlink whose target resolves to something other than a directory.
     Symlink,
+    /// Symlink whose target resolves to a directory.
+    SymlinkDir,
+    /// Symlink whose target does not exist or cannot be resolved.
+    BrokenSymlink,
+    Fifo,
+    Socket,
+    BlockDevice,
+    CharDevice,
+}
+
+impl EntryKind {
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Dir => "dir",
+            Self::Symlink => "symlink",
+            Self::SymlinkDir => "symlink_dir",
+            Self::BrokenSymlink => "broken_symlink",
+            Self::Fifo => "fifo",
+            Self::Socket => "socket",
+            Self::BlockDevice => "block_device",
+            Self::CharDevice => "char_device",
+        }
+    }
+
+    pub fn is_symlink(self) -> bool {
+        matches!(self, Self::Symlink | Self::SymlinkDir | Self::BrokenSymlink)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct FileEntry {
     pub path: PathBuf,
-    pub name: String,
+    /// File name exactly as the OS returned it; may not be valid UTF-8.
+    pub name: OsString,
     pub kind: EntryKind,
     pub mtime: SystemTime,
     pub size: Option<u64>,
     pub symlink_target: Option<PathBuf>,
+    pub hash: Option<String>,
+    pub long: Option<LongMetadata>,
+}
+
+/// Unix ownership and inode details collected only for `--long`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LongMetadata {
+    pub mode: u32,
+    pub nlink: u64,
+    pub inode: u64,
+    pub uid: u32,
+    pub gid: u32,
+    pub owner: String,
+    pub group: String,
 }
 
 impl FileEntry {
@@ -24,7 +69,15 @@ impl FileEntry {
     }
 
     pub fn is_symlink(&self) -> bool {
-        self.kind == EntryKind::Symlink
+        self.kind.is_symlink()
+    }
+
+    /// Path relative to the scanned directory, falling back to the bare name.
+    pub fn relative_path(&self, base: &Path) -> &OsStr {
+        self.path
+            .strip_prefix(base)
+            .map(Path::as_os_str)
+            .unwrap_or(&self.name)
     }
 }
 
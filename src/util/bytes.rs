@@ -0,0 +1,59 @@
+use std::ffi::OsStr;
+
+/// Render an OS string as UTF-8, writing each byte that is not part of a valid
+/// UTF-8 sequence as `\xNN` instead of collapsing it to U+FFFD.
+///
+/// Valid UTF-8 is returned unchanged. Once a name needs escaping, its literal
+/// backslashes become `\\` too, so the escaped form decodes back to the exact
+/// bytes.
+pub fn escape_invalid_utf8(value: &OsStr) -> String {
+    if let Some(text) = value.to_str() {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    for chunk in value.as_encoded_bytes().utf8_chunks() {
+        out.push_str(&chunk.valid().replace('\\', r"\\"));
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out
+}
+
+/// The bytes the OS uses for `value`; on Unix these are the exact filename bytes.
+pub fn os_bytes(value: &OsStr) -> &[u8] {
+    value.as_encoded_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_keeps_valid_names_unchanged() {
+        assert_eq!(escape_invalid_utf8(OsStr::new("résumé.txt")), "résumé.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn escape_marks_each_invalid_byte() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"caf\xe9-\xff\xfe.txt");
+        assert_eq!(escape_invalid_utf8(name), "caf\\xe9-\\xff\\xfe.txt");
+        assert_eq!(os_bytes(name), b"caf\xe9-\xff\xfe.txt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn escape_doubles_backslashes_only_in_escaped_names() {
+        use std::os::unix::ffi::OsStrExt;
+
+        assert_eq!(escape_invalid_utf8(OsStr::new(r"a\b")), r"a\b");
+        assert_eq!(
+            escape_invalid_utf8(OsStr::from_bytes(b"a\\b\xff")),
+            r"a\\b\xff"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use crate::model::FileEntry;
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Write};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Placeholders, longest first so `{//}` is not read as `{/}`.
+const PLACEHOLDERS: [(&str, Placeholder); 5] = [
+    ("{//}", Placeholder::Dir),
+    ("{/.}", Placeholder::NameNoExt),
+    ("{/}", Placeholder::Name),
+    ("{.}", Placeholder::PathNoExt),
+    ("{}", Placeholder::Path),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    /// `{}`: the path as listed, e.g. `src/main.rs`
+    Path,
+    /// `{/}`: the file name, `main.rs`
+    Name,
+    /// `{//}`: the parent directory, `src`
+    Dir,
+    /// `{.}`: the path without its extension, `src/main`
+    PathNoExt,
+    /// `{/.}`: the file name without its extension, `main`
+    NameNoExt,
+}
+
+impl Placeholder {
+    fn value(self, path: &Path) -> OsString {
+        match self {
+            Self::Path => path.as_os_str().to_os_string(),
+            Self::Name => path.file_name().unwrap_or(path.as_os_str()).to_os_string(),
+            Self::Dir => match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.as_os_str().to_os_string(),
+                _ => OsString::from("."),
+            },
+            Self::PathNoExt => path.with_extension("").into_os_string(),
+            Self::NameNoExt => path.file_stem().unwrap_or(path.as_os_str()).to_os_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Text(OsString),
+    Placeholder(Placeholder),
+}
+
+/// A command line for `--exec` / `--exec-batch` with its placeholders parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandTemplate {
+    args: Vec<Vec<Token>>,
+}
+
+impl CommandTemplate {
+    /// Like `fd`, a command without any placeholder gets `{}` appended.
+    pub fn parse(flag: &str, args: &[OsString]) -> Result<Self> {
+        if args.is_empty() || args[0].is_empty() {
+            bail!("{flag} needs a command to run");
+        }
+
+        let mut args: Vec<Vec<Token>> = args.iter().map(|arg| parse_arg(arg)).collect();
+        if !args
+            .iter()
+            .flatten()
+            .any(|token| matches!(token, Token::Placeholder(_)))
+        {
+            args.push(vec![Token::Placeholder(Placeholder::Path)]);
+        }
+        Ok(Self { args })
+    }
+
+    /// The command line for one entry.
+    fn expand(&self, path: &Path) -> Vec<OsString> {
+        self.args.iter().map(|arg| expand_arg(arg, path)).collect()
+    }
+
+    /// The command line for all entries at once: an argument with a
+    /// placeholder is repeated once per path, in order.
+    fn expand_batch(&self, paths: &[&Path]) -> Vec<OsString> {
+        let mut out = Vec::new();
+        for arg in &self.args {
+            if has_placeholder(arg) {
+                out.extend(paths.iter().map(|path| expand_arg(arg, path)));
+            } else {
+                out.push(expand_arg(arg, Path::new("")));
+            }
+        }
+        out
+    }
+
+    /// Split `paths` into runs whose batch command lines stay within
+    /// `budget` bytes, like `xargs`. A path too long for any batch still
+    /// gets a command line of its own.
+    fn batches<'a>(&self, paths: &[&'a Path], budget: usize) -> Vec<Vec<&'a Path>> {
+        let fixed: usize = self
+            .args
+            .iter()
+            .filter(|arg| !has_placeholder(arg))
+            .map(|arg| arg_cost(&expand_arg(arg, Path::new(""))))
+            .sum();
+
+        let mut batches: Vec<Vec<&Path>> = Vec::new();
+        let mut used = fixed;
+        for path in paths {
+            let cost: usize = self
+                .args
+                .iter()
+                .filter(|arg| has_placeholder(arg))
+                .map(|arg| arg_cost(&expand_arg(arg, path)))
+                .sum();
+            match batches.last_mut() {
+                Some(batch) if used + cost <= budget => batch.push(path),
+                _ => {
+                    batches.push(vec![path]);
+                    used = fixed;
+                }
+            }
+            used += cost;
+        }
+        batches
+    }
+}
+
+fn has_placeholder(arg: &[Token]) -> bool {
+    arg.iter()
+        .any(|token| matches!(token, Token::Placeholder(_)))
+}
+
+/// Bytes one argument takes from the `exec` limit: its text, the
+/// terminating NUL, and the `argv` pointer.
+fn arg_cost(arg: &OsStr) -> usize {
+    arg.len() + 1 + std::mem::size_of::<usize>()
+}
+
+/// Command-line budget for one `--exec-batch` run. Like `xargs`, stay well
+/// under `ARG_MAX` so the environment still fits; Windows caps a whole
+/// command line at 32767 UTF-16 units.
+const BATCH_ARG_BUDGET: usize = if cfg!(windows) { 24 * 1024 } else { 128 * 1024 };
+
+fn parse_arg(arg: &OsStr) -> Vec<Token> {
+    // Placeholders are ASCII, so a name that is not UTF-8 is kept as literal text.
+    let Some(mut rest) = arg.to_str() else {
+        return vec![Token::Text(arg.to_os_string())];
+    };
+
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    while !rest.is_empty() {
+        if let Some((pattern, placeholder)) = PLACEHOLDERS
+            .iter()
+            .find(|(pattern, _)| rest.starts_with(pattern))
+        {
+            if !text.is_empty() {
+                tokens.push(Token::Text(OsString::from(std::mem::take(&mut text))));
+            }
+            tokens.push(Token::Placeholder(*placeholder));
+            rest = &rest[pattern.len()..];
+        } else {
+            let ch = rest.chars().next().unwrap_or_default();
+            text.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    if !text.is_empty() || tokens.is_empty() {
+        tokens.push(Token::Text(OsString::from(text)));
+    }
+    tokens
+}
+
+fn expand_arg(arg: &[Token], path: &Path) -> OsString {
+    let mut out = OsString::new();
+    for token in arg {
+        match token {
+            Token::Text(text) => out.push(text),
+            Token::Placeholder(placeholder) => out.push(placeholder.value(path)),
+        }
+    }
+    out
+}
+
+/// `--exec`: run the command once per entry, several at a time, and print
+/// each command's output in entry order once it and all earlier ones finish.
+pub fn run_each(template: &CommandTemplate, entries: &[FileEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let jobs = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(entries.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut failed = 0;
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(entry) = entries.get(index) else {
+                        break;
+                    };
+                    let result = output(&template.expand(&entry.path));
+                    if sender.send((index, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_to_print) {
+                if !print_result(result) {
+                    failed += 1;
+                }
+                next_to_print += 1;
+            }
+        }
+    });
+
+    if failed > 0 {
+        bail!("--exec: {failed} of {} commands failed", entries.len());
+    }
+    Ok(())
+}
+
+/// `--exec-batch`: run the command with every entry, split into as few runs
+/// as the command-line limit allows. The command keeps the terminal, so
+/// interactive programs such as editors work.
+pub fn run_batch(template: &CommandTemplate, entries: &[FileEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let paths: Vec<&Path> = entries.iter().map(|entry| entry.path.as_path()).collect();
+    let batches = template.batches(&paths, BATCH_ARG_BUDGET);
+    let mut failed = Vec::new();
+    for batch in &batches {
+        let args = template.expand_batch(batch);
+        let status = Command::new(&args[0])
+            .args(&args[1..])
+            .status()
+            .with_context(|| {
+                format!(
+                    "--exec-batch: failed to run `{}`",
+                    args[0].to_string_lossy()
+                )
+            })?;
+        if !status.success() {
+            failed.push((args[0].to_string_lossy().into_owned(), status));
+        }
+    }
+
+    match failed.as_slice() {
+        [] => Ok(()),
+        [(program, status)] if batches.len() == 1 => {
+            bail!("--exec-batch: `{program}` failed ({status})")
+        }
+        _ => bail!(
+            "--exec-batch: {} of {} commands failed",
+            failed.len(),
+            batches.len()
+        ),
+    }
+}
+
+fn output(args: &[OsString]) -> Result<Output> {
+    Command::new(&args[0])
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("--exec: failed to run `{}`", args[0].to_string_lossy()))
+}
+
+/// Copy one command's captured output through; `false` when it failed.
+fn print_result(result: Result<Output>) -> bool {
+    match result {
+        Ok(output) => {
+            // A closed stdout (e.g. `| head`) should not turn into a failure here.
+            let _ = io::stdout().lock().write_all(&output.stdout);
+            let _ = io::stderr().lock().write_all(&output.stderr);
+            output.status.success()
+        }
+        Err(err) => {
+            eprintln!("{err:#}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(args: &[&str]) -> CommandTemplate {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        CommandTemplate::parse("--exec", &args).unwrap()
+    }
+
+    #[test]
+    fn placeholders_expand_path_name_dir_and_stems() {
+        let command = template(&["echo", "{}", "{/}", "{//}", "{.}", "{/.}", "x{/}.bak"]);
+        assert_eq!(
+            command.expand(Path::new("src/main.rs")),
+            [
+                "echo",
+                "src/main.rs",
+                "main.rs",
+                "src",
+                "src/main",
+                "main",
+                "xmain.rs.bak"
+            ]
+        );
+        assert_eq!(command.expand(Path::new("notes"))[3], ".");
+    }
+
+    #[test]
+    fn a_command_without_placeholders_gets_the_path_appended() {
+        assert_eq!(
+            template(&["wc", "-l"]).expand(Path::new("a.txt")),
+            ["wc", "-l", "a.txt"]
+        );
+        assert_eq!(
+            template(&["echo", "{", "}"]).expand(Path::new("a")),
+            ["echo", "{", "}", "a"]
+        );
+    }
+
+    #[test]
+    fn batch_repeats_placeholder_arguments_per_path() {
+        let command = template(&["tar", "czf", "out.tgz", "{}"]);
+        assert_eq!(
+            command.expand_batch(&[Path::new("a"), Path::new("b")]),
+            ["tar", "czf", "out.tgz", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn batches_split_paths_to_stay_under_the_budget() {
+        let command = template(&["rm", "{}"]);
+        let paths = [Path::new("aaaa"), Path::new("bbbb"), Path::new("cccc")];
+        let fixed = arg_cost(OsStr::new("rm"));
+        let per_path = arg_cost(OsStr::new("aaaa"));
+
+        let all = command.batches(&paths, fixed + 3 * per_path);
+        assert_eq!(all, [paths.to_vec()]);
+
+        let split = command.batches(&paths, fixed + 2 * per_path);
+        assert_eq!(split, [vec![paths[0], paths[1]], vec![paths[2]]]);
+
+        // A path that can never fit still runs, alone.
+        let tiny = command.batches(&paths, 1);
+        assert_eq!(tiny.len(), 3);
+    }
+
+    #[test]
+    fn parse_rejects_an_empty_command() {
+        let err = CommandTemplate::parse("--exec-batch", &[]).unwrap_err();
+        assert_eq!(err.to_string(), "--exec-batch needs a command to run");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::model::{EntryKind, FileEntry};
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, Read};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::thread;
+
+/// Files larger than this are skipped unless `--hash-max-size` says otherwise.
+pub const DEFAULT_HASH_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HashAlgorithm {
+    Blake3,
+    Sha256,
+    Xxh3,
+}
+
+impl HashAlgorithm {
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Blake3 => "blake3",
+            Self::Sha256 => "sha256",
+            Self::Xxh3 => "xxh3",
+        }
+    }
+}
+
+/// Fill `FileEntry.hash` for regular files at or below `max_size`.
+///
+/// Work is split across the available cores; entries keep their order because
+/// each worker writes into its own slice. Unreadable files keep `hash: None`.
+pub fn hash_entries(entries: &mut [FileEntry], algorithm: HashAlgorithm, max_size: u64) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let workers = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    let chunk_size = entries.len().div_ceil(workers).max(1);
+
+    thread::scope(|scope| {
+        for chunk in entries.chunks_mut(chunk_size) {
+            scope.spawn(move || {
+                for entry in chunk {
+                    if should_hash(entry, max_size) {
+                        entry.hash = hash_file(&entry.path, algorithm).ok();
+                    }
+                }
+            });
+        }
+    });
+}
+
+fn should_hash(entry: &FileEntry, max_size: u64) -> bool {
+    entry.kind == EntryKind::File && entry.size.is_some_and(|size| size <= max_size)
+}
+
+/// Digest a file and return it as `<algorithm>:<lowercase hex>`.
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> io::Result<String> {
+    hash_reader(&mut File::open(path)?, algorithm)
+}
+
+/// Digest everything `reader` yields, in the same format as [`hash_file`].
+pub fn hash_reader(reader: &mut impl Read, algorithm: HashAlgorithm) -> io::Result<String> {
+    let digest = match algorithm {
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            read_chunks(reader, |chunk| {
+                hasher.update(chunk);
+            })?;
+            hasher.finalize().as_bytes().to_vec()
+        }
+        HashAlgorithm::Sha256 => {
+            let mut hasher = Sha256::new();
+            read_chunks(reader, |chunk| hasher.update(chunk))?;
+            hasher.finalize().to_vec()
+        }
+        HashAlgorithm::Xxh3 => {
+            let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+            read_chunks(reader, |chunk| hasher.update(chunk))?;
+            hasher.digest().to_be_bytes().to_vec()
+        }
+    };
+
+    Ok(format!("{}:{}", algorithm.key(), to_hex(&digest)))
+}
+
+fn read_chunks(reader: &mut impl Read, mut consume: impl FnMut(&[u8])) -> io::Result<()> {
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        consume(&buffer[..read]);
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Parse a byte size such as `1048576`, `512K`, `64M`, `1G`, or `64MiB`.
+pub fn parse_size(input: &str) -> Result<u64> {
+    let value = input.trim();
+    let digits = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(digits);
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        _ => 0,
+    };
+
+    let bytes = amount
+        .parse::<u64>()
+        .ok()
+        .filter(|_| multiplier > 0)
+        .and_then(|amount| amount.checked_mul(multiplier));
+
+    match bytes {
+        Some(bytes) => Ok(bytes),
+        None => bail!(
+            "invalid value for --hash-max-size: `{value}` (supported: 1048576, 512K, 64M, 1G)"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+    use tempfile::tempdir;
+
+    fn file_entry(path: PathBuf, size: u64) -> FileEntry {
+        FileEntry {
+            name: path.file_name().unwrap().to_os_string(),
+            path,
+            kind: EntryKind::File,
+            mtime: SystemTime::now(),
+            size: Some(size),
+            symlink_target: None,
+            hash: None,
+            long: None,
+        }
+    }
+
+    #[test]
+    fn hash_file_matches_known_digests() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("abc.txt");
+        fs::write(&path, b"abc")?;
+
+        assert_eq!(
+            hash_file(&path, HashAlgorithm::Sha256)?,
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hash_file(&path, HashAlgorithm::Blake3)?,
+            "blake3:6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(
+            hash_file(&path, HashAlgorithm::Xxh3)?,
+            "xxh3:78af5f94892f3950"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hash_entries_skips_large_files_and_non_files() -> Result<()> {
+        let dir = tempdir()?;
+        let small = dir.path().join("small.txt");
+        let large = dir.path().join("large.bin");
+        fs::write(&small, b"abc")?;
+        fs::write(&large, vec![0u8; 32])?;
+
+        let mut entries = vec![
+            file_entry(small, 3),
+            file_entry(large, 32),
+            FileEntry {
+                kind: EntryKind::Dir,
+                size: None,
+                ..file_entry(dir.path().to_path_buf(), 0)
+            },
+        ];
+        hash_entries(&mut entries, HashAlgorithm::Sha256, 16);
+
+        assert!(entries[0].hash.is_some());
+        assert!(entries[1].hash.is_none());
+        assert!(entries[2].hash.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_size_accepts_binary_suffixes() {
+        assert_eq!(parse_size("1048576").unwrap(), 1_048_576);
+        assert_eq!(parse_size("512K").unwrap(), 512 * 1024);
+        assert_eq!(parse_size("64MiB").unwrap(), 64 * 1024 * 1024);
+        assert_eq!(parse_size("1g").unwrap(), 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parse_size_rejects_unknown_units() {
+        let err = parse_size("12parsecs").unwrap_err().to_string();
+        assert!(err.contains("invalid value for --hash-max-size"));
+        assert!(parse_size("").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use clap_mangen::Man;
+use clap_mangen::roff::{Inline, Roff, bold, roman};
+
+/// The hand-written contract. Its sections after the flag list (behavior,
+/// validation rules, output shapes, environment) become man page sections, so
+/// the manual cannot drift from `docs/CLI.md`.
+const CONTRACT: &str = include_str!("../../docs/CLI.md");
+
+/// Contract sections left out of the man page: the signature and the flag
+/// list, which clap renders from the `Cli` definition instead.
+const SKIPPED_SECTIONS: [&str; 2] = ["1", "3"];
+
+/// `ftime man`: the roff source of `ftime(1)`.
+pub fn render(command: clap::Command) -> Result<Vec<u8>> {
+    let mut command = command.disable_help_subcommand(true);
+    command.build();
+    let man = Man::new(command.clone())
+        .title("FTIME")
+        .source(format!("ftime {}", env!("CARGO_PKG_VERSION")))
+        .manual("User Commands");
+
+    let mut out = Vec::new();
+    man.render_title(&mut out)?;
+    man.render_name_section(&mut out)?;
+    man.render_synopsis_section(&mut out)?;
+    man.render_description_section(&mut out)?;
+    if let Some(after_help) = command.get_after_help() {
+        let mut roff = Roff::new();
+        roff.control("PP", []).text([roman(after_help.to_string())]);
+        roff.to_writer(&mut out)?;
+    }
+    man.render_options_section(&mut out)?;
+
+    let mut roff = Roff::new();
+    render_commands(&mut roff, &mut command);
+    render_contract(&mut roff, CONTRACT);
+    roff.control("SH", ["EXIT STATUS"]);
+    roff.control("TP", [])
+        .text([bold("0")])
+        .text([roman("Success.")]);
+    roff.control("TP", []).text([bold("1")]).text([roman(
+        "A validation, scan, or update error; the message is printed to stderr.",
+    )]);
+    roff.control("TP", []).text([bold("2")]).text([roman(
+        "The command line could not be parsed (unknown flag or invalid value).",
+    )]);
+    roff.to_writer(&mut out)?;
+
+    man.render_version_section(&mut out)?;
+    Ok(out)
+}
+
+/// Subcommands with their full usage line, instead of the `ftime-name(1)`
+/// pages clap_mangen would point to.
+fn render_commands(roff: &mut Roff, command: &mut clap::Command) {
+    roff.control("SH", ["COMMANDS"]);
+    for sub in command.get_subcommands_mut() {
+        let usage = sub.render_usage().to_string();
+        let usage = usage.strip_prefix("Usage: ").unwrap_or(&usage);
+        roff.control("TP", []).text([bold(usage)]);
+        if let Some(about) = sub.get_about() {
+            roff.text([roman(about.to_string())]);
+        }
+    }
+}
+
+/// Turn the `## N. Title` sections of the contract into `.SH` and the
+/// `## Na. Title` ones into `.SS`; bullets become indented paragraphs and
+/// backquoted text is set in bold.
+fn render_contract(roff: &mut Roff, contract: &str) {
+    let mut skipping = true;
+    let mut code = false;
+    for line in contract.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            let (number, title) = heading.split_once(". ").unwrap_or(("", heading));
+            let top_level = number.chars().all(|ch| ch.is_ascii_digit());
+            let section = number.trim_end_matches(|ch: char| ch.is_ascii_lowercase());
+            skipping = SKIPPED_SECTIONS.contains(&section);
+            if skipping {
+                continue;
+            }
+            if top_level {
+                roff.control("SH", [title.to_uppercase().as_str()]);
+            } else {
+                roff.control("SS", [title]);
+            }
+            continue;
+        }
+        if skipping {
+            continue;
+        }
+
+        if line.starts_with("```") {
+            if code {
+                roff.control("fi", []).control("RE", []);
+            } else {
+                roff.control("RS", []).control("nf", []);
+            }
+            code = !code;
+        } else if code {
+            roff.text([roman(line)]);
+        } else if let Some(item) = line.strip_prefix("- ") {
+            roff.control("IP", ["\\(bu", "2"]).text(inlines(item));
+        } else if !line.trim().is_empty() {
+            roff.control("PP", []).text(inlines(line));
+        }
+    }
+}
+
+/// Markdown code spans (`` `--plain` ``) in bold, the rest in roman.
+fn inlines(text: &str) -> Vec<Inline> {
+    text.split('`')
+        .enumerate()
+        .filter(|(_, part)| !part.is_empty())
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                bold(part)
+            } else {
+                roman(part)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract_roff(contract: &str) -> String {
+        let mut roff = Roff::new();
+        render_contract(&mut roff, contract);
+        roff.to_roff()
+    }
+
+    #[test]
+    fn contract_headings_become_sections_and_skip_the_flag_list() {
+        let roff = contract_roff(
+            "# Title\n\n## 3. Flags\n\n- `--plain`: x\n\n## 4. Validation Rules\n\n- a\n\n## 4a. More Rules\n\ntext\n",
+        );
+        assert!(!roff.contains("plain"), "{roff}");
+        assert!(roff.contains(".SH \"VALIDATION RULES\""), "{roff}");
+        assert!(roff.contains(".SS \"More Rules\""), "{roff}");
+        assert!(roff.contains(".PP\ntext"), "{roff}");
+    }
+
+    #[test]
+    fn code_spans_are_bold_and_dashes_escaped() {
+        let roff = contract_roff("## 9. Environment\n\n- `NO_COLOR` disables `--color auto`\n");
+        assert!(
+            roff.contains(".IP \\(bu 2\n\\fBNO_COLOR\\fR disables \\fB\\-\\-color auto\\fR"),
+            "{roff}"
+        );
+    }
+}
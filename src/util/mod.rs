@@ -1,3 +1,10 @@
+pub mod bytes;
+pub mod exec;
+pub mod hash;
 pub mod ignore;
+pub mod man;
+#[cfg(unix)]
+pub mod owner;
+pub mod shell;
 pub mod time;
 pub mod update;
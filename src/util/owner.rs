@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::fs;
+
+/// Local uid/gid to name lookup backed by `/etc/passwd` and `/etc/group`.
+///
+/// Ids without a local entry (for example NSS-only or container-mapped users)
+/// fall back to their numeric form, the same way `ls -l` does.
+#[derive(Debug, Clone, Default)]
+pub struct OwnerNames {
+    users: HashMap<u32, String>,
+    groups: HashMap<u32, String>,
+}
+
+impl OwnerNames {
+    pub fn load() -> Self {
+        Self {
+            users: fs::read_to_string("/etc/passwd")
+                .map(|contents| parse_id_table(&contents))
+                .unwrap_or_default(),
+            groups: fs::read_to_string("/etc/group")
+                .map(|contents| parse_id_table(&contents))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn user(&self, uid: u32) -> String {
+        self.users
+            .get(&uid)
+            .cloned()
+            .unwrap_or_else(|| uid.to_string())
+    }
+
+    pub fn group(&self, gid: u32) -> String {
+        self.groups
+            .get(&gid)
+            .cloned()
+            .unwrap_or_else(|| gid.to_string())
+    }
+}
+
+/// Both files share the `name:password:id:...` prefix.
+fn parse_id_table(contents: &str) -> HashMap<u32, String> {
+    let mut table = HashMap::new();
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split(':');
+        let (Some(name), Some(_), Some(id)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        let Ok(id) = id.parse() else { continue };
+
+        table.entry(id).or_insert_with(|| name.to_string());
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_id_table_reads_passwd_and_group_lines() {
+        let passwd = "# comment\nroot:x:0:0:root:/root:/bin/bash\nalice:x:1000:1000::/home/alice:/bin/sh\nbroken-line\n";
+        let table = parse_id_table(passwd);
+        assert_eq!(table.get(&0).map(String::as_str), Some("root"));
+        assert_eq!(table.get(&1000).map(String::as_str), Some("alice"));
+        assert_eq!(table.len(), 2);
+
+        let group = "wheel:x:10:alice,bob\n";
+        assert_eq!(
+            parse_id_table(group).get(&10).map(String::as_str),
+            Some("wheel")
+        );
+    }
+
+    #[test]
+    fn unknown_ids_fall_back_to_numbers() {
+        let names = OwnerNames::default();
+        assert_eq!(names.user(4242), "4242");
+        assert_eq!(names.group(7), "7");
+    }
+}
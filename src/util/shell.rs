@@ -0,0 +1,156 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+/// Shells `ftime shell-init` has widgets for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Write the completion script for `shell`, generated from the CLI definition.
+pub fn print_completions(shell: Shell, command: &mut clap::Command) -> Result<()> {
+    // Generate into a buffer first: `clap_complete` panics on a closed stdout.
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(shell, command, name, &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+/// The script to `eval` (or `source` in fish) from the shell's startup file.
+pub fn init_script(shell: InitShell) -> &'static str {
+    match shell {
+        InitShell::Bash => BASH_INIT,
+        InitShell::Zsh => ZSH_INIT,
+        InitShell::Fish => FISH_INIT,
+    }
+}
+
+// The picker reads NUL-terminated `--picker --print0` records, whose first
+// field is the exact absolute path; the last three fields never contain a
+// tab, so stripping them recovers any name, even one with tabs or newlines.
+// `fcd` reads the same records without color and takes the first one whose
+// type field is `[DIR]` or `[LND]`. Both call `command ftime` to skip
+// aliases that add flags.
+
+const BASH_INIT: &str = r#"# ftime shell integration for bash: eval "$(ftime shell-init bash)"
+
+# Ctrl-T: pick recent entries with fzf and insert them at the cursor.
+__ftime_select() {
+  local record tab=$'\t' picked=""
+  while IFS= read -r -d '' record; do
+    picked+="$(printf '%q' "${record%$tab*$tab*$tab*}") "
+  done < <(command ftime --picker --print0 | fzf --read0 --print0 --ansi --multi --delimiter '\t' --with-nth -3.. --preview 'command ftime preview {..-4}' --height=40% --reverse --prompt='recent> ')
+  READLINE_LINE="${READLINE_LINE:0:READLINE_POINT}${picked}${READLINE_LINE:READLINE_POINT}"
+  READLINE_POINT=$((READLINE_POINT + ${#picked}))
+}
+
+# fcd [DIR]: cd into the most recently modified subdirectory of DIR.
+fcd() {
+  local base="${1:-.}" record type target="" tab=$'\t'
+  # Read to the end so ftime never writes into a closed pipe.
+  while IFS= read -r -d '' record; do
+    [ -n "$target" ] && continue
+    type="${record%$tab*}"
+    type="${type##*$tab}"
+    if [ "$type" = '[DIR]' ] || [ "$type" = '[LND]' ]; then
+      target="${record%$tab*$tab*$tab*}"
+    fi
+  done < <(command ftime --picker --print0 --color never "$base")
+  if [ -z "$target" ]; then
+    echo "fcd: no subdirectory in $base" >&2
+    return 1
+  fi
+  cd -- "$target"
+}
+
+if [[ $- == *i* ]] && command -v fzf >/dev/null 2>&1; then
+  bind -m emacs-standard -x '"\C-t": __ftime_select'
+  bind -m vi-insert -x '"\C-t": __ftime_select'
+fi
+"#;
+
+const ZSH_INIT: &str = r#"# ftime shell integration for zsh: eval "$(ftime shell-init zsh)"
+
+# Ctrl-T: pick recent entries with fzf and insert them at the cursor.
+__ftime_select() {
+  local record tab=$'\t'
+  local -a picked
+  for record in "${(@0)$(command ftime --picker --print0 | fzf --read0 --print0 --ansi --multi --delimiter '\t' --with-nth -3.. --preview 'command ftime preview {..-4}' --height=40% --reverse --prompt='recent> ')}"; do
+    [[ -n $record ]] && picked+=("${record%$tab*$tab*$tab*}")
+  done
+  if (( ${#picked} )); then
+    LBUFFER+="${(j: :)${(@q)picked}} "
+  fi
+  zle reset-prompt
+}
+
+# fcd [DIR]: cd into the most recently modified subdirectory of DIR.
+fcd() {
+  local base="${1:-.}" record type target="" tab=$'\t'
+  for record in "${(@0)$(command ftime --picker --print0 --color never "$base")}"; do
+    type="${record%$tab*}"
+    type="${type##*$tab}"
+    if [[ $type == '[DIR]' || $type == '[LND]' ]]; then
+      target="${record%$tab*$tab*$tab*}"
+      break
+    fi
+  done
+  if [[ -z "$target" ]]; then
+    echo "fcd: no subdirectory in $base" >&2
+    return 1
+  fi
+  cd -- "$target"
+}
+
+if [[ -o interactive ]] && (( $+commands[fzf] )); then
+  zle -N __ftime_select
+  bindkey -M emacs '^T' __ftime_select
+  bindkey -M viins '^T' __ftime_select
+fi
+"#;
+
+const FISH_INIT: &str = r#"# ftime shell integration for fish: ftime shell-init fish | source
+
+# Ctrl-T: pick recent entries with fzf and insert them at the cursor.
+function __ftime_select
+    set -l picked
+    for record in (command ftime --picker --print0 | fzf --read0 --print0 --ansi --multi --delimiter '\t' --with-nth -3.. --preview 'command ftime preview {..-4}' --height=40% --reverse --prompt='recent> ' | string split0)
+        set -a picked (string replace -r '(\t[^\t]*){3}$' '' -- $record | string collect)
+    end
+    if test (count $picked) -gt 0
+        commandline -it -- (string join ' ' (string escape -- $picked))' '
+    end
+    commandline -f repaint
+end
+
+# fcd [DIR]: cd into the most recently modified subdirectory of DIR.
+function fcd --description 'cd into the most recently modified subdirectory'
+    set -l base .
+    if set -q argv[1]
+        set base $argv[1]
+    end
+    set -l target
+    for record in (command ftime --picker --print0 --color never $base | string split0)
+        set -l type (string replace -r '(?s)^.*\t([^\t]*)\t[^\t]*$' '$1' -- $record | string collect)
+        if contains -- $type '[DIR]' '[LND]'
+            set target (string replace -r '(\t[^\t]*){3}$' '' -- $record | string collect)
+            break
+        end
+    end
+    if test -z "$target"
+        echo "fcd: no subdirectory in $base" >&2
+        return 1
+    end
+    cd $target
+end
+
+if status is-interactive; and type -q fzf
+    bind \ct __ftime_select
+    bind -M insert \ct __ftime_select
+end
+"#;
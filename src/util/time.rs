@@ -1,5 +1,5 @@
 use anyhow::{Result, bail};
-use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use std::time::{Duration, SystemTime};
 
 /// Compute the bucket for a given modification time.
@@ -56,9 +56,14 @@ pub fn utc_rfc3339(mtime: SystemTime) -> String {
 }
 
 pub fn parse_since(input: &str, now: SystemTime) -> Result<SystemTime> {
+    parse_time_bound("--since", input, now)
+}
+
+/// Parse a `--since`-style lower bound, naming `flag` in error messages.
+pub fn parse_time_bound(flag: &str, input: &str, now: SystemTime) -> Result<SystemTime> {
     let value = input.trim();
     if value.is_empty() {
-        bail!("invalid value for --since: expected duration or date");
+        bail!("invalid value for {flag}: expected duration or date");
     }
 
     if let Some(ts) = parse_duration_since(value, now) {
@@ -70,16 +75,24 @@ pub fn parse_since(input: &str, now: SystemTime) -> Result<SystemTime> {
     }
 
     bail!(
-        "invalid value for --since: `{value}` (supported: 15m, 24h, 7d, 2026-04-13, RFC3339/local datetime)"
+        "invalid value for {flag}: `{value}` (supported: 15m, 24h, 7d, 2026-04-13, RFC3339/local datetime)"
     )
 }
 
-#[allow(dead_code)]
 pub fn start_of_day(ts: SystemTime) -> SystemTime {
     let dt: DateTime<Local> = ts.into();
     start_of_local_day(dt).into()
 }
 
+/// Start of the local clock hour containing `ts`.
+pub fn start_of_hour(ts: SystemTime) -> SystemTime {
+    let dt: DateTime<Local> = ts.into();
+    dt.with_minute(0)
+        .and_then(|dt| dt.with_second(0))
+        .and_then(|dt| dt.with_nanosecond(0))
+        .map_or(ts, SystemTime::from)
+}
+
 fn start_of_local_day(now: DateTime<Local>) -> DateTime<Local> {
     let date = now.date_naive();
     let Some(naive) = date.and_hms_opt(0, 0, 0) else {
@@ -321,4 +334,23 @@ mod tests {
         let err = parse_since("nonsense", now).unwrap_err().to_string();
         assert!(err.contains("invalid value for --since"));
     }
+
+    #[test]
+    fn test_parse_time_bound_names_its_flag() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(10_000);
+        let err = parse_time_bound("--tree-collapse", "nonsense", now)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid value for --tree-collapse"));
+    }
+
+    #[test]
+    fn test_start_of_hour_truncates_minutes_and_seconds() {
+        let now = SystemTime::now();
+        let hour = start_of_hour(now);
+        let dt: DateTime<Local> = hour.into();
+        assert!(hour <= now);
+        assert!(now.duration_since(hour).unwrap() < Duration::from_secs(3600));
+        assert_eq!((dt.minute(), dt.second(), dt.nanosecond()), (0, 0, 0));
+    }
 }
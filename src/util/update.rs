@@ -1,16 +1,26 @@
+use crate::util::hash::{HashAlgorithm, hash_reader};
 use anyhow::{Context, Result, bail};
+use minisign_verify::{PublicKey, Signature};
 use serde_json::Value;
 use std::env;
-use std::io::Write;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const LATEST_RELEASE_API_URL: &str = "https://api.github.com/repos/tsutomu-n/ftime/releases/latest";
+const RELEASE_DOWNLOAD_URL: &str = "https://github.com/tsutomu-n/ftime/releases/download";
 
-#[cfg(unix)]
-const INSTALLER_ASSET_NAME: &str = "ftime-install.sh";
-#[cfg(windows)]
-const INSTALLER_ASSET_NAME: &str = "ftime-install.ps1";
+/// `sha256sum` output covering every archive of a release.
+const CHECKSUMS_ASSET_NAME: &str = "SHA256SUMS";
+/// minisign signature of `SHA256SUMS`.
+const SIGNATURE_ASSET_NAME: &str = "SHA256SUMS.minisig";
+
+/// minisign public key the release workflow embeds at build time. When set,
+/// `--self-update` refuses releases whose `SHA256SUMS` is not signed with it.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("FTIME_RELEASE_PUBLIC_KEY");
 
 pub fn self_update() -> Result<()> {
     let current_exe = env::current_exe().context("failed to resolve current executable path")?;
@@ -26,13 +36,12 @@ pub fn self_update() -> Result<()> {
         );
     }
     let previous_version = read_binary_version(&current_exe);
+    let binary_name = current_exe
+        .file_name()
+        .context("failed to resolve installed binary name")?;
 
-    run_platform_update(&install_dir)?;
-    let installed_exe = install_dir.join(
-        current_exe
-            .file_name()
-            .context("failed to resolve installed binary name")?,
-    );
+    install_release(&latest_published_version()?, &install_dir, binary_name)?;
+    let installed_exe = install_dir.join(binary_name);
     let current_version = read_binary_version(&installed_exe);
     println!(
         "{}",
@@ -56,24 +65,6 @@ pub fn check_for_update() -> Result<()> {
     Ok(())
 }
 
-fn installer_url() -> Result<String> {
-    if let Some(url) = env::var("FTIME_SELF_UPDATE_URL")
-        .ok()
-        .filter(|url| !url.trim().is_empty())
-    {
-        return Ok(url);
-    }
-
-    Ok(installer_url_for_version(&latest_published_version()?))
-}
-
-fn installer_url_for_version(version: &str) -> String {
-    let version = version.trim_start_matches('v');
-    format!(
-        "https://github.com/tsutomu-n/ftime/releases/download/v{version}/{INSTALLER_ASSET_NAME}"
-    )
-}
-
 fn current_binary_version(executable: &Path) -> Option<String> {
     env::var("FTIME_SELF_UPDATE_CURRENT_VERSION")
         .ok()
@@ -371,63 +362,312 @@ fn is_known_non_profile_dir(segment: &std::ffi::OsStr) -> bool {
     )
 }
 
-#[cfg(unix)]
-fn run_platform_update(install_dir: &Path) -> Result<()> {
-    let url = installer_url()?;
-    let installer = Command::new("curl")
-        .arg("-fsSL")
-        .arg(&url)
-        .output()
-        .with_context(|| format!("failed to download installer from {url}"))?;
+/// Download the release archive and `SHA256SUMS` into a staging directory,
+/// verify them, and only then swap the binary in `install_dir`.
+fn install_release(version: &str, install_dir: &Path, binary_name: &OsStr) -> Result<()> {
+    let archive_name = release_archive_name(version)?;
+    let base_url = release_base_url(version);
+    let staging = StagingDir::create()?;
 
-    if !installer.status.success() {
-        bail!("failed to download installer from {url}");
+    let checksums_path = staging.path().join(CHECKSUMS_ASSET_NAME);
+    download(
+        &format!("{base_url}/{CHECKSUMS_ASSET_NAME}"),
+        &checksums_path,
+    )
+    .context("refusing to update without a checksums file")?;
+    let checksums = fs::read(&checksums_path).context("failed to read SHA256SUMS")?;
+
+    if let Some(public_key) = release_public_key() {
+        let signature_path = staging.path().join(SIGNATURE_ASSET_NAME);
+        download(
+            &format!("{base_url}/{SIGNATURE_ASSET_NAME}"),
+            &signature_path,
+        )
+        .context("refusing to update without a signature for SHA256SUMS")?;
+        let signature =
+            fs::read_to_string(&signature_path).context("failed to read SHA256SUMS.minisig")?;
+        verify_signature(&checksums, &signature, &public_key)?;
     }
 
-    let mut bash = Command::new("bash")
-        .env("INSTALL_DIR", install_dir)
-        .stdin(Stdio::piped())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .context("failed to start installer shell")?;
+    let archive_path = staging.path().join(&archive_name);
+    download(&format!("{base_url}/{archive_name}"), &archive_path)?;
+    // Verify and extract the same in-memory bytes, so the file cannot change
+    // between the two steps.
+    let archive =
+        fs::read(&archive_path).with_context(|| format!("failed to read {archive_name}"))?;
+    verify_checksum(
+        &String::from_utf8_lossy(&checksums),
+        &archive_name,
+        &archive,
+    )?;
+
+    let extract_dir = staging.path().join("extract");
+    extract_archive(&archive, &archive_name, &extract_dir)?;
+    let new_binary = extract_dir.join(format!("ftime{}", env::consts::EXE_SUFFIX));
+    if !new_binary.is_file() {
+        bail!("release archive {archive_name} does not contain ftime");
+    }
+
+    replace_binary(&new_binary, &install_dir.join(binary_name))
+}
+
+fn release_base_url(version: &str) -> String {
+    if let Some(url) = env::var("FTIME_SELF_UPDATE_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+    {
+        return url.trim_end_matches('/').to_string();
+    }
+
+    format!(
+        "{RELEASE_DOWNLOAD_URL}/v{}",
+        version.trim_start_matches('v')
+    )
+}
+
+/// The archive the release workflow builds for this platform, e.g.
+/// `ftime-2.0.8-x86_64-unknown-linux-gnu.tar.gz`.
+fn release_archive_name(version: &str) -> Result<String> {
+    let Some(target) = release_target() else {
+        bail!(
+            "--self-update has no prebuilt release for {}-{}",
+            env::consts::OS,
+            env::consts::ARCH
+        );
+    };
+    let extension = if cfg!(windows) { "zip" } else { "tar.gz" };
+    Ok(format!(
+        "ftime-{}-{target}.{extension}",
+        version.trim_start_matches('v')
+    ))
+}
+
+fn release_target() -> Option<&'static str> {
+    match (env::consts::OS, env::consts::ARCH) {
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        ("windows", "x86_64") => Some("x86_64-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+/// The key embedded by the release build. `FTIME_SELF_UPDATE_PUBLIC_KEY` is
+/// only consulted by builds without one, so the environment cannot replace
+/// the trust anchor of a signed release.
+fn release_public_key() -> Option<String> {
+    let embedded = RELEASE_PUBLIC_KEY
+        .map(str::trim)
+        .filter(|key| !key.is_empty());
+    if let Some(key) = embedded {
+        return Some(key.to_string());
+    }
+    env::var("FTIME_SELF_UPDATE_PUBLIC_KEY")
+        .ok()
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Check `archive` against its line in a `sha256sum`-style checksums file.
+fn verify_checksum(checksums: &str, archive_name: &str, archive: &[u8]) -> Result<()> {
+    let Some(expected) = checksums.lines().find_map(|line| {
+        let (digest, name) = line.split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        (name.strip_prefix('*').unwrap_or(name) == archive_name).then_some(digest)
+    }) else {
+        bail!("refusing to update: {archive_name} is not listed in SHA256SUMS");
+    };
+
+    let actual = hash_reader(&mut &archive[..], HashAlgorithm::Sha256)
+        .with_context(|| format!("failed to hash {archive_name}"))?;
+    let actual = actual.trim_start_matches("sha256:");
+    if !expected.eq_ignore_ascii_case(actual) {
+        bail!(
+            "refusing to update: SHA-256 mismatch for {archive_name} (expected {expected}, got {actual})"
+        );
+    }
+    Ok(())
+}
+
+/// Check a minisign signature of the checksums file. `public_key` is the
+/// base64 line of a `minisign.pub` file.
+fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let public_key = PublicKey::from_base64(public_key)
+        .map_err(|err| anyhow::anyhow!("invalid release public key: {err}"))?;
+    let signature = Signature::decode(signature)
+        .map_err(|err| anyhow::anyhow!("refusing to update: invalid SHA256SUMS.minisig: {err}"))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|err| anyhow::anyhow!("refusing to update: bad signature on SHA256SUMS: {err}"))
+}
+
+#[cfg(unix)]
+fn download(url: &str, destination: &Path) -> Result<()> {
+    let status = Command::new("curl")
+        .arg("-fsSL")
+        .arg("-o")
+        .arg(destination)
+        .arg(url)
+        .status()
+        .with_context(|| format!("failed to download {url}"))?;
 
-    let stdin = bash
-        .stdin
-        .as_mut()
-        .context("failed to open installer stdin")?;
-    stdin
-        .write_all(&installer.stdout)
-        .context("failed to write installer script to bash")?;
-    let _ = bash.stdin.take();
-
-    let status = bash.wait().context("failed to wait for installer")?;
     if !status.success() {
-        bail!("self-update installer failed");
+        bail!("failed to download {url}");
     }
     Ok(())
 }
 
 #[cfg(windows)]
-fn run_platform_update(install_dir: &Path) -> Result<()> {
-    let installer_url = installer_url()?;
+fn download(url: &str, destination: &Path) -> Result<()> {
     let status = Command::new("powershell")
         .arg("-NoProfile")
         .arg("-ExecutionPolicy")
         .arg("Bypass")
         .arg("-Command")
-        .arg("& ([scriptblock]::Create((Invoke-WebRequest -Uri $env:FTIME_SELF_UPDATE_URL -UseBasicParsing).Content)) -InstallDir $env:FTIME_SELF_UPDATE_INSTALL_DIR")
-        .env("FTIME_SELF_UPDATE_URL", installer_url)
-        .env("FTIME_SELF_UPDATE_INSTALL_DIR", install_dir)
+        .arg("Invoke-WebRequest -Uri $env:FTIME_DOWNLOAD_URL -OutFile $env:FTIME_DOWNLOAD_PATH -UseBasicParsing")
+        .env("FTIME_DOWNLOAD_URL", url)
+        .env("FTIME_DOWNLOAD_PATH", destination)
         .status()
-        .context("failed to start PowerShell installer")?;
+        .with_context(|| format!("failed to download {url}"))?;
 
     if !status.success() {
-        bail!("self-update installer failed");
+        bail!("failed to download {url}");
     }
     Ok(())
 }
 
+/// `tar` reads both the `.tar.gz` archives and, on Windows, the `.zip` ones.
+/// The verified bytes are piped to it rather than passed as a path; GNU tar
+/// cannot detect gzip on a pipe, hence the explicit `-z`.
+fn extract_archive(archive: &[u8], archive_name: &str, destination: &Path) -> Result<()> {
+    fs::create_dir(destination)
+        .with_context(|| format!("failed to create {}", destination.display()))?;
+    let mode = if cfg!(windows) { "-xf" } else { "-xzf" };
+    let mut child = Command::new("tar")
+        .arg(mode)
+        .arg("-")
+        .arg("-C")
+        .arg(destination)
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("failed to run tar")?;
+    let written = child
+        .stdin
+        .take()
+        .context("failed to open tar stdin")?
+        .write_all(archive);
+    let status = child.wait().context("failed to run tar")?;
+
+    if written.is_err() || !status.success() {
+        bail!("failed to extract {archive_name}");
+    }
+    Ok(())
+}
+
+/// Copy the new binary next to `target` and rename it into place, so a failed
+/// copy never leaves a half-written `ftime` behind.
+fn replace_binary(new_binary: &Path, target: &Path) -> Result<()> {
+    let install_dir = target
+        .parent()
+        .context("failed to resolve install directory")?;
+    let file_name = target
+        .file_name()
+        .context("failed to resolve installed binary name")?;
+    let mut staged_name = OsString::from(".");
+    staged_name.push(file_name);
+    staged_name.push(".new");
+    let staged = install_dir.join(staged_name);
+
+    fs::copy(new_binary, &staged)
+        .with_context(|| format!("failed to write {}", staged.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("failed to make {} executable", staged.display()))?;
+    }
+    // A running executable cannot be replaced on Windows, but it can be renamed.
+    #[cfg(windows)]
+    if target.exists() {
+        let old = target.with_extension("exe.old");
+        let _ = fs::remove_file(&old);
+        fs::rename(target, &old)
+            .with_context(|| format!("failed to move {} aside", target.display()))?;
+    }
+
+    if let Err(err) = fs::rename(&staged, target) {
+        let _ = fs::remove_file(&staged);
+        return Err(err).with_context(|| format!("failed to replace {}", target.display()));
+    }
+    Ok(())
+}
+
+/// A private temporary directory for downloads, removed when dropped.
+struct StagingDir {
+    path: PathBuf,
+}
+
+impl StagingDir {
+    /// Create a fresh directory under an unpredictable name. Never reuses an
+    /// existing one, which another user could own in a shared temp dir.
+    fn create() -> Result<Self> {
+        let temp_dir = env::temp_dir();
+        for attempt in 0u32..16 {
+            let path = temp_dir.join(format!("ftime-update-{:016x}", staging_nonce(attempt)));
+            match create_private_dir(&path) {
+                Ok(()) => return Ok(Self { path }),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to create {}", path.display()));
+                }
+            }
+        }
+        bail!(
+            "failed to create a staging directory in {}",
+            temp_dir.display()
+        )
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Mix the clock, pid and an ASLR-randomized address into a name suffix.
+fn staging_nonce(attempt: u32) -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+    let local = 0u8;
+    let address = &local as *const u8 as u64;
+    let mut state = nanos
+        ^ (u64::from(std::process::id()) << 32)
+        ^ address.rotate_left(17)
+        ^ u64::from(attempt).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    // splitmix64 finalizer
+    state = (state ^ (state >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    state ^ (state >> 31)
+}
+
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    fs::DirBuilder::new().mode(0o700).create(path)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    fs::create_dir(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -437,13 +677,78 @@ mod tests {
     static ENV_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
-    fn installer_url_for_version_points_to_versioned_release_asset() {
-        let url = installer_url_for_version("1.2.3");
+    fn release_archive_name_matches_the_release_workflow_assets() {
+        let Some(target) = release_target() else {
+            assert!(release_archive_name("1.2.3").is_err());
+            return;
+        };
+        let extension = if cfg!(windows) { "zip" } else { "tar.gz" };
         assert_eq!(
-            url,
-            format!(
-                "https://github.com/tsutomu-n/ftime/releases/download/v1.2.3/{INSTALLER_ASSET_NAME}"
-            )
+            release_archive_name("v1.2.3").unwrap(),
+            format!("ftime-1.2.3-{target}.{extension}")
+        );
+    }
+
+    #[test]
+    fn verify_checksum_accepts_a_matching_sha256sum_line() {
+        let archive = b"abc";
+        let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+        let checksums = format!("0000  other.tar.gz\n{digest} *ftime.tar.gz\n");
+        assert!(verify_checksum(&checksums, "ftime.tar.gz", archive).is_ok());
+
+        let err = verify_checksum(
+            &format!("{}  ftime.tar.gz\n", "0".repeat(64)),
+            "ftime.tar.gz",
+            archive,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.starts_with("refusing to update: SHA-256 mismatch for ftime.tar.gz"),
+            "{err}"
+        );
+        assert!(err.contains(digest), "{err}");
+
+        let err = verify_checksum("0000  other.tar.gz\n", "ftime.tar.gz", archive)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "refusing to update: ftime.tar.gz is not listed in SHA256SUMS"
+        );
+    }
+
+    #[test]
+    fn staging_dir_is_fresh_and_private() {
+        let first = StagingDir::create().unwrap();
+        let second = StagingDir::create().unwrap();
+        assert_ne!(first.path(), second.path());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(first.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn verify_signature_rejects_malformed_keys_and_signatures() {
+        let err = verify_signature(b"data", "", "not a key")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("invalid release public key"), "{err}");
+
+        let key = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+        let err = verify_signature(b"data", "untrusted comment: x\ngarbage\n", key)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("refusing to update: invalid SHA256SUMS.minisig"),
+            "{err}"
         );
     }
 
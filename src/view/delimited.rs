@@ -0,0 +1,151 @@
+use crate::model::FileEntry;
+use crate::util::bytes::os_bytes;
+use crate::view::template::{PLACEHOLDERS, field_value};
+use anyhow::{Result, bail};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Columns used when `--columns` is not given; mirrors the JSON Lines fields.
+pub const DEFAULT_COLUMNS: [&str; 6] = ["path", "bucket", "mtime", "relative_time", "kind", "size"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    Csv,
+    Tsv,
+}
+
+pub struct DelimitedOptions<'a> {
+    pub delimiter: Delimiter,
+    pub columns: &'a [&'static str],
+    pub header: bool,
+}
+
+/// Parse a comma-separated `--columns` list against the `--format` placeholder set.
+pub fn parse_columns(input: &str) -> Result<Vec<&'static str>> {
+    let mut columns = Vec::new();
+
+    for name in input
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        let Some(column) = PLACEHOLDERS.iter().find(|column| **column == name) else {
+            bail!(
+                "invalid value for --columns: unknown column `{name}` (supported: {})",
+                PLACEHOLDERS.join(", ")
+            );
+        };
+        columns.push(*column);
+    }
+
+    if columns.is_empty() {
+        bail!("invalid value for --columns: expected at least one column");
+    }
+
+    Ok(columns)
+}
+
+pub fn render(
+    entries: &[FileEntry],
+    now: SystemTime,
+    base: &Path,
+    options: DelimitedOptions<'_>,
+) -> Result<()> {
+    let mut writer = BufWriter::new(std::io::stdout());
+
+    if options.header {
+        let header: Vec<String> = options.columns.iter().map(|c| c.to_string()).collect();
+        write_record(&mut writer, options.delimiter, &header)?;
+    }
+
+    for entry in entries {
+        let record: Vec<String> = options
+            .columns
+            .iter()
+            .map(|column| field_value(column, entry, now, base))
+            .collect();
+        write_record(&mut writer, options.delimiter, &record)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write entry paths separated by NUL bytes for `xargs -0`.
+pub fn render_print0(entries: &[FileEntry], base: &Path) -> Result<()> {
+    let mut writer = BufWriter::new(std::io::stdout());
+    for entry in entries {
+        writer.write_all(os_bytes(entry.relative_path(base)))?;
+        writer.write_all(b"\0")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_record(writer: &mut impl Write, delimiter: Delimiter, fields: &[String]) -> Result<()> {
+    let line = match delimiter {
+        Delimiter::Csv => {
+            let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            format!("{}\r\n", fields.join(","))
+        }
+        Delimiter::Tsv => {
+            let fields: Vec<String> = fields.iter().map(|field| tsv_field(field)).collect();
+            format!("{}\n", fields.join("\t"))
+        }
+    };
+    writer.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// RFC 4180: quote fields containing a comma, quote, CR, or LF and double
+/// embedded quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// TSV cannot quote, so tabs, newlines, and backslashes are escaped instead.
+fn tsv_field(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("plain.txt"), "plain.txt");
+        assert_eq!(csv_field("a,b.txt"), "\"a,b.txt\"");
+        assert_eq!(csv_field("say \"hi\".txt"), "\"say \"\"hi\"\".txt\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn tsv_field_escapes_separators() {
+        assert_eq!(tsv_field("a\tb\nc\\d"), "a\\tb\\nc\\\\d");
+    }
+
+    #[test]
+    fn parse_columns_validates_names() {
+        assert_eq!(parse_columns("path, size").unwrap(), vec!["path", "size"]);
+
+        let err = parse_columns("path,colour").unwrap_err().to_string();
+        assert!(err.contains("unknown column `colour`"), "{err}");
+        assert!(parse_columns(" , ").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::util::bytes::os_bytes;
+use clap::ValueEnum;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{MAIN_SEPARATOR, Path};
+
+/// When names in the human view become OSC 8 hyperlinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HyperlinkMode {
+    /// Link when stdout is a terminal that is not `TERM=dumb`
+    Auto,
+    Always,
+    Never,
+}
+
+pub fn should_hyperlink(mode: HyperlinkMode) -> bool {
+    match mode {
+        HyperlinkMode::Always => true,
+        HyperlinkMode::Never => false,
+        HyperlinkMode::Auto => {
+            std::io::stdout().is_terminal()
+                && std::env::var_os("TERM").is_none_or(|term| term != "dumb")
+        }
+    }
+}
+
+/// Builds `file://host/abs/path` targets for one run.
+#[derive(Debug, Clone)]
+pub struct Linker {
+    host: String,
+}
+
+impl Linker {
+    /// Uses the local hostname so terminals can tell local files from remote
+    /// ones (for example over SSH); an unknown host leaves it empty.
+    pub fn new() -> Self {
+        Self::with_host(local_hostname().unwrap_or_default())
+    }
+
+    pub fn with_host(host: impl Into<String>) -> Self {
+        Self { host: host.into() }
+    }
+
+    pub fn url(&self, path: &Path) -> Option<String> {
+        let absolute = std::path::absolute(path).ok()?;
+        let mut url = format!("file://{}", percent_encode(self.host.as_bytes()));
+        let bytes = os_bytes(absolute.as_os_str());
+        if MAIN_SEPARATOR == '\\' {
+            // `C:\dir\file` becomes `/C:/dir/file`.
+            let slashed: Vec<u8> = bytes
+                .iter()
+                .map(|byte| if *byte == b'\\' { b'/' } else { *byte })
+                .collect();
+            if slashed.first() != Some(&b'/') {
+                url.push('/');
+            }
+            url.push_str(&percent_encode(&slashed));
+        } else {
+            url.push_str(&percent_encode(bytes));
+        }
+        Some(url)
+    }
+}
+
+impl Default for Linker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wrap already-styled `text` in an OSC 8 link. The escapes take no cells, so
+/// callers pad by the display width of the text alone.
+pub fn wrap(text: &str, url: &str) -> String {
+    format!("\u{1b}]8;;{url}\u{1b}\\{text}\u{1b}]8;;\u{1b}\\")
+}
+
+/// Percent-encode everything except RFC 3986 unreserved bytes, `/`, and `:`.
+fn percent_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for byte in bytes {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                out.push(char::from(*byte))
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+fn local_hostname() -> Option<String> {
+    ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .into_iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn url_percent_encodes_the_absolute_path() {
+        let linker = Linker::with_host("box");
+        assert_eq!(
+            linker.url(Path::new("/tmp/my notes#1.md")).as_deref(),
+            Some("file://box/tmp/my%20notes%231.md")
+        );
+        assert_eq!(
+            Linker::with_host("").url(Path::new("/a/ü")).as_deref(),
+            Some("file:///a/%C3%BC")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn url_keeps_invalid_utf8_bytes() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/x/caf\xe9"));
+        assert_eq!(
+            Linker::with_host("h").url(path).as_deref(),
+            Some("file://h/x/caf%E9")
+        );
+    }
+
+    #[test]
+    fn wrap_surrounds_the_text_with_osc_8() {
+        assert_eq!(
+            wrap("a.rs", "file:///a.rs"),
+            "\u{1b}]8;;file:///a.rs\u{1b}\\a.rs\u{1b}]8;;\u{1b}\\"
+        );
+    }
+}
@@ -1,8 +1,41 @@
-use crate::model::TimeBucket;
+use crate::model::{EntryKind, FileEntry, TimeBucket};
+use clap::ValueEnum;
 
 /// アイコン取得のためのインタフェース。
 pub trait IconProvider {
     fn bucket_icon(&self, bucket: TimeBucket) -> &'static str;
+
+    /// 行の先頭に置くエントリ単位のアイコン。空文字ならアイコン列を出さない。
+    fn entry_icon(&self, entry: &FileEntry) -> &'static str;
+}
+
+/// `--icons` で選べるアイコンセット。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IconSet {
+    /// Nerd Font glyphs (icons builds only)
+    #[cfg(feature = "icons")]
+    Nerd,
+    /// Emoji that need no special font
+    Emoji,
+    /// Plain ASCII markers
+    Ascii,
+}
+
+/// 値なしの `--icons` が選ぶセット。Nerd Font は `icons` feature のビルドだけ。
+#[cfg(feature = "icons")]
+pub const DEFAULT_ICON_SET: &str = "nerd";
+#[cfg(not(feature = "icons"))]
+pub const DEFAULT_ICON_SET: &str = "emoji";
+
+/// `--icons` の指定に対応するプロバイダ。未指定ならアイコンを出さない。
+pub fn provider(icons: Option<IconSet>) -> &'static dyn IconProvider {
+    match icons {
+        #[cfg(feature = "icons")]
+        Some(IconSet::Nerd) => &NerdIconProvider,
+        Some(IconSet::Emoji) => &EmojiIconProvider,
+        Some(IconSet::Ascii) => &AsciiIconProvider,
+        None => &DefaultIconProvider,
+    }
 }
 
 /// デフォルトではアイコンを出さない。
@@ -12,6 +45,99 @@ impl IconProvider for DefaultIconProvider {
     fn bucket_icon(&self, _bucket: TimeBucket) -> &'static str {
         ""
     }
+
+    fn entry_icon(&self, _entry: &FileEntry) -> &'static str {
+        ""
+    }
+}
+
+/// プロバイダ共通のエントリ分類。種別を先に見て、次に既知のファイル名、最後に拡張子。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileIcon {
+    Dir,
+    Symlink,
+    SymlinkDir,
+    BrokenSymlink,
+    Fifo,
+    Socket,
+    Device,
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Go,
+    C,
+    Shell,
+    Web,
+    Markdown,
+    Config,
+    Docker,
+    Package,
+    Lock,
+    Git,
+    License,
+    Image,
+    Pdf,
+    Archive,
+    Text,
+    File,
+}
+
+impl FileIcon {
+    pub fn classify(entry: &FileEntry) -> Self {
+        match entry.kind {
+            EntryKind::Dir => return Self::Dir,
+            EntryKind::Symlink => return Self::Symlink,
+            EntryKind::SymlinkDir => return Self::SymlinkDir,
+            EntryKind::BrokenSymlink => return Self::BrokenSymlink,
+            EntryKind::Fifo => return Self::Fifo,
+            EntryKind::Socket => return Self::Socket,
+            EntryKind::BlockDevice | EntryKind::CharDevice => return Self::Device,
+            EntryKind::File => {}
+        }
+
+        let name = entry.name.to_string_lossy();
+        if let Some(icon) = Self::from_file_name(&name) {
+            return icon;
+        }
+
+        let ext = name
+            .rsplit_once('.')
+            .filter(|(stem, _)| !stem.is_empty())
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("rs") => Self::Rust,
+            Some("py" | "pyi") => Self::Python,
+            Some("js" | "mjs" | "cjs" | "jsx") => Self::JavaScript,
+            Some("ts" | "tsx") => Self::TypeScript,
+            Some("go") => Self::Go,
+            Some("c" | "h" | "cc" | "cpp" | "hpp") => Self::C,
+            Some("sh" | "bash" | "zsh" | "fish" | "ps1") => Self::Shell,
+            Some("html" | "htm" | "css" | "scss") => Self::Web,
+            Some("md" | "markdown" | "rst") => Self::Markdown,
+            Some("toml" | "yaml" | "yml" | "json" | "ini" | "conf" | "cfg") => Self::Config,
+            Some("lock") => Self::Lock,
+            Some("png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "ico") => Self::Image,
+            Some("pdf") => Self::Pdf,
+            Some("zip" | "tar" | "gz" | "tgz" | "xz" | "zst" | "bz2" | "7z") => Self::Archive,
+            Some("txt" | "log" | "csv" | "tsv") => Self::Text,
+            _ => Self::File,
+        }
+    }
+
+    fn from_file_name(name: &str) -> Option<Self> {
+        let icon = match name {
+            "Cargo.toml" | "Cargo.lock" => Self::Rust,
+            "Dockerfile" | "Containerfile" | "compose.yaml" | "docker-compose.yml" => Self::Docker,
+            "package.json" | "go.mod" | "pyproject.toml" => Self::Package,
+            "Makefile" | "justfile" | "Justfile" => Self::Shell,
+            ".gitignore" | ".gitattributes" | ".gitmodules" => Self::Git,
+            _ if name.starts_with("LICENSE") || name.starts_with("COPYING") => Self::License,
+            _ if name.starts_with("README") => Self::Markdown,
+            _ => return None,
+        };
+        Some(icon)
+    }
 }
 
 /// Nerd Fontsを想定したアイコンセット。
@@ -32,4 +158,176 @@ impl IconProvider for NerdIconProvider {
             TimeBucket::History => "󱎓",  // history/clock
         }
     }
+
+    fn entry_icon(&self, entry: &FileEntry) -> &'static str {
+        match FileIcon::classify(entry) {
+            FileIcon::Dir => "\u{f07b}",           // nf-fa-folder
+            FileIcon::Symlink => "\u{f481}",       // nf-oct-file_symlink_file
+            FileIcon::SymlinkDir => "\u{f482}",    // nf-oct-file_symlink_directory
+            FileIcon::BrokenSymlink => "\u{f127}", // nf-fa-chain_broken
+            FileIcon::Fifo => "\u{f0ec}",          // nf-fa-exchange
+            FileIcon::Socket => "\u{f1e6}",        // nf-fa-plug
+            FileIcon::Device => "\u{f0a0}",        // nf-fa-hdd_o
+            FileIcon::Rust => "\u{e7a8}",          // nf-dev-rust
+            FileIcon::Python => "\u{e73c}",        // nf-dev-python
+            FileIcon::JavaScript => "\u{e74e}",    // nf-dev-javascript
+            FileIcon::TypeScript => "\u{e628}",    // nf-seti-typescript
+            FileIcon::Go => "\u{e626}",            // nf-seti-go
+            FileIcon::C => "\u{e61e}",             // nf-custom-c
+            FileIcon::Shell => "\u{f489}",         // nf-oct-terminal
+            FileIcon::Web => "\u{e736}",           // nf-dev-html5
+            FileIcon::Markdown => "\u{e73e}",      // nf-dev-markdown
+            FileIcon::Config => "\u{e615}",        // nf-seti-config
+            FileIcon::Docker => "\u{f308}",        // nf-linux-docker
+            FileIcon::Package => "\u{f487}",       // nf-oct-package
+            FileIcon::Lock => "\u{f023}",          // nf-fa-lock
+            FileIcon::Git => "\u{f1d3}",           // nf-fa-git
+            FileIcon::License => "\u{f0e3}",       // nf-fa-legal
+            FileIcon::Image => "\u{f1c5}",         // nf-fa-file_image_o
+            FileIcon::Pdf => "\u{f1c1}",           // nf-fa-file_pdf_o
+            FileIcon::Archive => "\u{f410}",       // nf-oct-file_zip
+            FileIcon::Text => "\u{f15c}",          // nf-fa-file_text
+            FileIcon::File => "\u{f016}",          // nf-fa-file_o
+        }
+    }
+}
+
+/// 特別なフォントなしで表示できる絵文字セット。幅 2 の文字だけを使う。
+pub struct EmojiIconProvider;
+
+impl IconProvider for EmojiIconProvider {
+    fn bucket_icon(&self, bucket: TimeBucket) -> &'static str {
+        match bucket {
+            TimeBucket::Active => "🔥",
+            TimeBucket::Today => "☕",
+            TimeBucket::ThisWeek => "📅",
+            TimeBucket::History => "⏳",
+        }
+    }
+
+    fn entry_icon(&self, entry: &FileEntry) -> &'static str {
+        match FileIcon::classify(entry) {
+            FileIcon::Dir => "📁",
+            FileIcon::Symlink | FileIcon::SymlinkDir => "🔗",
+            FileIcon::BrokenSymlink => "💔",
+            FileIcon::Fifo => "🚰",
+            FileIcon::Socket => "🔌",
+            FileIcon::Device => "💽",
+            FileIcon::Rust => "🦀",
+            FileIcon::Python => "🐍",
+            FileIcon::JavaScript => "🟨",
+            FileIcon::TypeScript => "🟦",
+            FileIcon::Go => "🐹",
+            FileIcon::C => "🔩",
+            FileIcon::Shell => "🐚",
+            FileIcon::Web => "🌐",
+            FileIcon::Markdown => "📝",
+            FileIcon::Config => "🔧",
+            FileIcon::Docker => "🐳",
+            FileIcon::Package => "📋",
+            FileIcon::Lock => "🔒",
+            FileIcon::Git => "🌱",
+            FileIcon::License => "📜",
+            FileIcon::Image => "🎨",
+            FileIcon::Pdf => "📕",
+            FileIcon::Archive => "📦",
+            FileIcon::Text => "📄",
+            FileIcon::File => "📄",
+        }
+    }
+}
+
+/// どの端末でも崩れない 1 文字の ASCII マーカー（`ls -F` に近い）。
+pub struct AsciiIconProvider;
+
+impl IconProvider for AsciiIconProvider {
+    fn bucket_icon(&self, bucket: TimeBucket) -> &'static str {
+        match bucket {
+            TimeBucket::Active => "*",
+            TimeBucket::Today => "+",
+            TimeBucket::ThisWeek => "~",
+            TimeBucket::History => ".",
+        }
+    }
+
+    fn entry_icon(&self, entry: &FileEntry) -> &'static str {
+        match FileIcon::classify(entry) {
+            FileIcon::Dir => "/",
+            FileIcon::Symlink | FileIcon::SymlinkDir => "@",
+            FileIcon::BrokenSymlink => "!",
+            FileIcon::Fifo => "|",
+            FileIcon::Socket => "=",
+            FileIcon::Device => "#",
+            FileIcon::Shell => "$",
+            FileIcon::Archive => "%",
+            FileIcon::Lock => "&",
+            _ => "-",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+    use unicode_width::UnicodeWidthStr;
+
+    fn entry(name: &str, kind: EntryKind) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(name),
+            name: OsString::from(name),
+            kind,
+            mtime: SystemTime::now(),
+            size: None,
+            symlink_target: None,
+            hash: None,
+            long: None,
+        }
+    }
+
+    #[test]
+    fn classify_prefers_kind_then_file_name_then_extension() {
+        assert_eq!(
+            FileIcon::classify(&entry("src.rs", EntryKind::Dir)),
+            FileIcon::Dir
+        );
+        assert_eq!(
+            FileIcon::classify(&entry("Cargo.toml", EntryKind::File)),
+            FileIcon::Rust
+        );
+        assert_eq!(
+            FileIcon::classify(&entry("Dockerfile", EntryKind::File)),
+            FileIcon::Docker
+        );
+        assert_eq!(
+            FileIcon::classify(&entry("config.TOML", EntryKind::File)),
+            FileIcon::Config
+        );
+        assert_eq!(
+            FileIcon::classify(&entry(".bashrc", EntryKind::File)),
+            FileIcon::File
+        );
+        assert_eq!(
+            FileIcon::classify(&entry("link", EntryKind::BrokenSymlink)),
+            FileIcon::BrokenSymlink
+        );
+    }
+
+    #[test]
+    fn fallback_icons_have_a_fixed_display_width() {
+        let samples = [
+            entry("d", EntryKind::Dir),
+            entry("main.rs", EntryKind::File),
+            entry("notes.md", EntryKind::File),
+            entry("a.tar", EntryKind::File),
+            entry("plain", EntryKind::File),
+            entry("pipe", EntryKind::Fifo),
+        ];
+        for sample in &samples {
+            assert_eq!(EmojiIconProvider.entry_icon(sample).width(), 2);
+            assert_eq!(AsciiIconProvider.entry_icon(sample).width(), 1);
+        }
+    }
 }
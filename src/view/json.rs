@@ -1,15 +1,30 @@
 #[cfg(feature = "json")]
+use base64::{Engine, prelude::BASE64_STANDARD};
+#[cfg(feature = "json")]
+use schemars::JsonSchema;
+#[cfg(feature = "json")]
 use serde::Serialize;
 
+#[cfg(feature = "json")]
+use crate::engine::{Bucketed, DotMode, ScanOptions, ScanStats};
 use crate::model::FileEntry;
+#[cfg(feature = "json")]
+use crate::util::bytes::{escape_invalid_utf8, os_bytes};
 use crate::util::time::{classify_bucket, relative_time, utc_rfc3339};
+#[cfg(feature = "json")]
+use crate::view::summary::{Summary, SummaryRow, Tally};
+#[cfg(feature = "json")]
+use crate::view::timeline::Timeline;
 use anyhow::Result;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::time::SystemTime;
 
+/// Newest JSON Lines schema accepted by `--json-version`; version 1 stays the default.
+pub const LATEST_JSON_LINES_VERSION: u8 = 2;
+
 #[cfg_attr(not(feature = "json"), allow(dead_code))]
-pub fn render(entries: &[FileEntry], now: SystemTime, base: &Path) -> Result<()> {
+pub fn render(entries: &[FileEntry], now: SystemTime, base: &Path, version: u8) -> Result<()> {
     #[cfg(not(feature = "json"))]
     {
         unreachable!("json feature not enabled");
@@ -20,7 +35,14 @@ pub fn render(entries: &[FileEntry], now: SystemTime, base: &Path) -> Result<()>
         let mut writer = BufWriter::new(std::io::stdout());
         for entry in entries {
             let record = JsonEntry::from_entry(entry, now, base);
-            let line = serde_json::to_string(&record)?;
+            let line = if version == 1 {
+                serde_json::to_string(&record)?
+            } else {
+                serde_json::to_string(&VersionedEntry {
+                    schema_version: version,
+                    entry: record,
+                })?
+            };
             writeln!(writer, "{line}")?;
         }
         writer.flush()?;
@@ -28,17 +50,330 @@ pub fn render(entries: &[FileEntry], now: SystemTime, base: &Path) -> Result<()>
     }
 }
 
+/// Print the JSON Schema (draft 2020-12) describing one JSON Lines record.
+#[cfg(feature = "json")]
+pub fn render_schema(version: u8) -> Result<()> {
+    let schema = if version == 1 {
+        schemars::schema_for!(JsonEntry)
+    } else {
+        schemars::schema_for!(VersionedEntry)
+    };
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    serde_json::to_writer_pretty(&mut writer, &schema)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Emit `--timeline --json` as one JSON object with the binned counts.
+#[cfg(feature = "json")]
+pub fn render_timeline(timeline: &Timeline) -> Result<()> {
+    let record = JsonTimeline {
+        bin: timeline.bin.key(),
+        bins: timeline
+            .bins
+            .iter()
+            .map(|bin| JsonTimelineBin {
+                start: utc_rfc3339(bin.start),
+                count: bin.count,
+            })
+            .collect(),
+        earlier: timeline.earlier,
+        total: timeline.total,
+    };
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonTimeline {
+    bin: &'static str,
+    bins: Vec<JsonTimelineBin>,
+    earlier: usize,
+    total: usize,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonTimelineBin {
+    start: String,
+    count: usize,
+}
+
+/// Emit `--summary --json` as one JSON object of counts and bytes.
+#[cfg(feature = "json")]
+pub fn render_summary(summary: &Summary) -> Result<()> {
+    let record = JsonSummary {
+        total: summary.totals.total().into(),
+        buckets: JsonBucketTallies::from_row(&summary.totals),
+        extensions: summary
+            .extensions
+            .iter()
+            .map(|row| JsonExtensionRow {
+                ext: row.key.clone(),
+                total: row.total().into(),
+                buckets: JsonBucketTallies::from_row(row),
+            })
+            .collect(),
+        directories: summary
+            .directories
+            .iter()
+            .map(|row| JsonDirectoryRow {
+                path: row.key.clone(),
+                unreadable: row.unreadable,
+                total: row.total().into(),
+                buckets: JsonBucketTallies::from_row(row),
+            })
+            .collect(),
+        stats: &summary.stats,
+    };
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    writeln!(writer, "{}", serde_json::to_string(&record)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    total: JsonTally,
+    buckets: JsonBucketTallies,
+    /// Busiest extensions first; `<dir>` groups directories and `(none)`
+    /// names without an extension.
+    extensions: Vec<JsonExtensionRow>,
+    /// `./` for the direct entries, then one row per top-level directory.
+    directories: Vec<JsonDirectoryRow>,
+    /// The scan's counts plus those of every directory read for `directories`.
+    stats: &'a ScanStats,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonExtensionRow {
+    /// Lowercased extension with a leading dot; control characters and
+    /// invalid UTF-8 are backslash-escaped.
+    ext: String,
+    total: JsonTally,
+    buckets: JsonBucketTallies,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonDirectoryRow {
+    /// Directory name with a trailing `/`, escaped like `ext`.
+    path: String,
+    /// Present and `true` when the directory could not be read.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    unreadable: bool,
+    total: JsonTally,
+    buckets: JsonBucketTallies,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonBucketTallies {
+    active: JsonTally,
+    today: JsonTally,
+    this_week: JsonTally,
+    history: JsonTally,
+}
+
+#[cfg(feature = "json")]
+impl JsonBucketTallies {
+    fn from_row(row: &SummaryRow) -> Self {
+        let [active, today, this_week, history] = row.buckets.map(|(_, tally)| tally.into());
+        Self {
+            active,
+            today,
+            this_week,
+            history,
+        }
+    }
+}
+
 #[cfg(feature = "json")]
 #[derive(Serialize)]
+struct JsonTally {
+    count: usize,
+    bytes: u64,
+}
+
+#[cfg(feature = "json")]
+impl From<Tally> for JsonTally {
+    fn from(tally: Tally) -> Self {
+        Self {
+            count: tally.count,
+            bytes: tally.bytes,
+        }
+    }
+}
+
+/// Version of the `--json-doc` document layout.
+#[cfg(feature = "json")]
+pub const DOCUMENT_SCHEMA_VERSION: u32 = 1;
+
+/// Emit the whole scan as one JSON document for `--json-doc`.
+#[cfg(feature = "json")]
+pub fn render_document(
+    bucketed: &Bucketed,
+    stats: &ScanStats,
+    scan_opts: &ScanOptions,
+    now: SystemTime,
+    base: &Path,
+) -> Result<()> {
+    let entries = |entries: &[FileEntry]| -> Vec<JsonEntry> {
+        entries
+            .iter()
+            .map(|entry| JsonEntry::from_entry(entry, now, base))
+            .collect()
+    };
+
+    let document = JsonDocument {
+        schema_version: DOCUMENT_SCHEMA_VERSION,
+        root: std::fs::canonicalize(base)
+            .unwrap_or_else(|_| base.to_path_buf())
+            .display()
+            .to_string(),
+        scanned_at: utc_rfc3339(now),
+        filters: JsonFilters::from_options(scan_opts),
+        stats,
+        buckets: JsonBuckets {
+            active: entries(&bucketed.active),
+            today: entries(&bucketed.today),
+            this_week: entries(&bucketed.week),
+            history: entries(&bucketed.history),
+        },
+    };
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    serde_json::to_writer_pretty(&mut writer, &document)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    root: String,
+    scanned_at: String,
+    filters: JsonFilters,
+    stats: &'a ScanStats,
+    buckets: JsonBuckets,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonFilters {
+    dots: &'static str,
+    ignore: bool,
+    ext: Option<Vec<String>>,
+    files_only: bool,
+    since: Option<String>,
+    follow: bool,
+}
+
+#[cfg(feature = "json")]
+impl JsonFilters {
+    fn from_options(scan_opts: &ScanOptions) -> Self {
+        Self {
+            dots: match scan_opts.dot_mode {
+                DotMode::Default => "default",
+                DotMode::All => "all",
+                DotMode::None => "none",
+            },
+            ignore: scan_opts.use_ignore,
+            ext: scan_opts.ext_filter.clone(),
+            files_only: scan_opts.files_only,
+            since: scan_opts.since_raw.clone(),
+            follow: scan_opts.follow_links,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonBuckets {
+    active: Vec<JsonEntry>,
+    today: Vec<JsonEntry>,
+    this_week: Vec<JsonEntry>,
+    history: Vec<JsonEntry>,
+}
+
+/// `--json-version 2` record: the v1 entry with a leading `schema_version`.
+#[cfg(feature = "json")]
+#[derive(Serialize, JsonSchema)]
+#[schemars(title = "ftime JSON Lines entry (v2)", deny_unknown_fields)]
+struct VersionedEntry {
+    /// JSON Lines schema version of this record.
+    #[schemars(extend("const" = 2))]
+    schema_version: u8,
+    #[serde(flatten)]
+    entry: JsonEntry,
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize, JsonSchema)]
+#[schemars(title = "ftime JSON Lines entry", deny_unknown_fields)]
 struct JsonEntry {
+    /// Path relative to the scanned directory; when it is not valid UTF-8 the
+    /// invalid bytes appear as `\xNN` and backslashes as `\\`.
     path: String,
+    /// Base64 of the exact path bytes; present only when the path is not valid UTF-8.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path_bytes: Option<String>,
+    /// Time bucket the entry falls into.
+    #[schemars(extend("enum" = ["active", "today", "this_week", "history"]))]
     bucket: String,
+    /// Modification time as UTC RFC3339.
     mtime: String,
+    /// Compact relative age such as `5m` or `3d`.
     relative_time: String,
     is_dir: bool,
+    /// `true` for every symlink kind.
     is_symlink: bool,
+    /// Entry type; a followed link reports its target's kind.
+    #[schemars(extend("enum" = [
+        "file",
+        "dir",
+        "symlink",
+        "symlink_dir",
+        "broken_symlink",
+        "fifo",
+        "socket",
+        "block_device",
+        "char_device",
+    ]))]
+    kind: &'static str,
+    /// Byte size; present only for regular files.
     #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<u64>,
+    /// `<algorithm>:<hex>` digest; present only with `--hash`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
+    /// Four-digit octal permissions; present only with `--long`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nlink: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inode: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    /// Link target, relative to the scanned directory when it lies inside it.
     #[serde(skip_serializing_if = "Option::is_none")]
     symlink_target: Option<String>,
 }
@@ -46,26 +381,37 @@ struct JsonEntry {
 #[cfg(feature = "json")]
 impl JsonEntry {
     fn from_entry(entry: &FileEntry, now: SystemTime, base: &Path) -> Self {
-        let path = entry
-            .path
-            .strip_prefix(base)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| entry.name.clone());
-
-        let symlink_target = entry.symlink_target.as_ref().map(|p| {
-            p.strip_prefix(base)
-                .map(|pp| pp.display().to_string())
-                .unwrap_or_else(|_| p.display().to_string())
-        });
+        let relative = entry.relative_path(base);
+        let path_bytes = relative
+            .to_str()
+            .is_none()
+            .then(|| BASE64_STANDARD.encode(os_bytes(relative)));
+
+        let symlink_target = entry
+            .symlink_target
+            .as_ref()
+            .map(|p| escape_invalid_utf8(p.strip_prefix(base).unwrap_or(p).as_os_str()));
+
+        let long = entry.long.as_ref();
 
         Self {
-            path,
+            path: escape_invalid_utf8(relative),
+            path_bytes,
             bucket: classify_bucket(now, entry.mtime).key().to_string(),
             mtime: utc_rfc3339(entry.mtime),
             relative_time: relative_time(now, entry.mtime),
             is_dir: entry.is_dir(),
             is_symlink: entry.is_symlink(),
+            kind: entry.kind.key(),
             size: entry.size,
+            hash: entry.hash.clone(),
+            mode: long.map(|long| format!("{:04o}", long.mode & 0o7777)),
+            nlink: long.map(|long| long.nlink),
+            inode: long.map(|long| long.inode),
+            uid: long.map(|long| long.uid),
+            gid: long.map(|long| long.gid),
+            owner: long.map(|long| long.owner.clone()),
+            group: long.map(|long| long.group.clone()),
             symlink_target,
         }
     }
@@ -0,0 +1,150 @@
+use crate::model::TimeBucket;
+use anyhow::{Result, bail};
+
+/// Rows each bucket shows in the human view; `None` shows every entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewLimits {
+    pub active: Option<usize>,
+    pub today: Option<usize>,
+    pub week: Option<usize>,
+    pub history: Option<usize>,
+}
+
+impl Default for PreviewLimits {
+    fn default() -> Self {
+        Self {
+            active: Some(20),
+            today: Some(20),
+            week: Some(20),
+            history: Some(5),
+        }
+    }
+}
+
+impl PreviewLimits {
+    pub fn get(&self, bucket: TimeBucket) -> Option<usize> {
+        match bucket {
+            TimeBucket::Active => self.active,
+            TimeBucket::Today => self.today,
+            TimeBucket::ThisWeek => self.week,
+            TimeBucket::History => self.history,
+        }
+    }
+
+    fn set(&mut self, bucket: TimeBucket, limit: Option<usize>) {
+        match bucket {
+            TimeBucket::Active => self.active = limit,
+            TimeBucket::Today => self.today = limit,
+            TimeBucket::ThisWeek => self.week = limit,
+            TimeBucket::History => self.history = limit,
+        }
+    }
+}
+
+/// A parsed `--limit` value: an optional cap on the newest entries overall
+/// plus any per-bucket preview overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub global: Option<usize>,
+    pub buckets: Vec<(TimeBucket, Option<usize>)>,
+}
+
+impl Limits {
+    /// Default previews with this value's per-bucket overrides applied.
+    pub fn preview(&self, all_history: bool) -> PreviewLimits {
+        let mut preview = PreviewLimits::default();
+        if all_history {
+            preview.history = None;
+        }
+        for (bucket, limit) in &self.buckets {
+            preview.set(*bucket, *limit);
+        }
+        preview
+    }
+}
+
+const BUCKETS: [TimeBucket; 4] = [
+    TimeBucket::Active,
+    TimeBucket::Today,
+    TimeBucket::ThisWeek,
+    TimeBucket::History,
+];
+
+/// Parse `--limit`: a comma-separated mix of `N` and `bucket=N|all`.
+pub fn parse_limits(input: &str) -> Result<Limits> {
+    let mut limits = Limits::default();
+
+    for item in input
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let Some((key, value)) = item.split_once('=') else {
+            match item.parse::<usize>() {
+                Ok(0) => bail!("invalid value for --limit: the overall limit must be at least 1"),
+                Ok(count) => limits.global = Some(count),
+                Err(_) => bail!(
+                    "invalid value for --limit: `{item}` (expected N or active|today|this_week|history=N|all)"
+                ),
+            }
+            continue;
+        };
+
+        let Some(bucket) = BUCKETS.iter().find(|bucket| bucket.key() == key.trim()) else {
+            bail!(
+                "invalid value for --limit: unknown bucket `{}` (supported: active, today, this_week, history)",
+                key.trim()
+            );
+        };
+        let limit = match value.trim() {
+            "all" => None,
+            count => match count.parse::<usize>() {
+                Ok(count) => Some(count),
+                Err(_) => bail!(
+                    "invalid value for --limit: `{item}` (expected {}=N or {}=all)",
+                    bucket.key(),
+                    bucket.key()
+                ),
+            },
+        };
+        limits.buckets.push((*bucket, limit));
+    }
+
+    if limits == Limits::default() {
+        bail!("invalid value for --limit: expected N or bucket=N");
+    }
+
+    Ok(limits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_global_and_bucket_limits() {
+        let limits = parse_limits("30, active=50,history=0,today=all").unwrap();
+        assert_eq!(limits.global, Some(30));
+        let preview = limits.preview(false);
+        assert_eq!(preview.active, Some(50));
+        assert_eq!(preview.today, None);
+        assert_eq!(preview.week, Some(20));
+        assert_eq!(preview.history, Some(0));
+
+        assert_eq!(
+            parse_limits("history=3").unwrap().preview(true).history,
+            Some(3)
+        );
+        assert_eq!(parse_limits("7").unwrap().preview(true).history, None);
+    }
+
+    #[test]
+    fn rejects_unknown_buckets_and_bad_counts() {
+        let err = parse_limits("week=3").unwrap_err().to_string();
+        assert!(err.contains("unknown bucket `week`"), "{err}");
+        let err = parse_limits("history=many").unwrap_err().to_string();
+        assert!(err.contains("history=N or history=all"), "{err}");
+        assert!(parse_limits("0").is_err());
+        assert!(parse_limits(",").is_err());
+    }
+}
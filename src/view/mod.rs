@@ -1,5 +1,15 @@
+pub mod delimited;
+pub mod hyperlink;
 pub mod icon;
 #[cfg(feature = "json")]
 pub mod json;
+pub mod limit;
+pub mod picker;
+pub mod quote;
+pub mod summary;
+pub mod template;
 pub mod text;
+pub mod theme;
+pub mod timeline;
+pub mod tree;
 pub mod tty;
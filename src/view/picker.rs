@@ -0,0 +1,204 @@
+use crate::engine::ScanResult;
+use crate::model::FileEntry;
+use crate::util::bytes::os_bytes;
+use crate::util::time::{absolute_time, classify_bucket, relative_time};
+use crate::view::quote::{QuotingStyle, quote};
+use crate::view::theme;
+use crate::view::tty::{
+    Alignment, ColorMode, format_byte_size, format_mode, format_name, format_size,
+    format_type_label, pad_to_display_width, style_time_text,
+};
+use anyhow::Result;
+use colored::Colorize;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::SystemTime;
+use unicode_width::UnicodeWidthStr;
+
+/// Children listed under `newest` on a directory's preview card.
+const PREVIEW_CHILDREN: usize = 5;
+/// Width of the field labels on the preview card.
+const CARD_LABEL_WIDTH: usize = 10;
+
+/// Fuzzy finders read from a pipe, so unlike the human view `auto` keeps
+/// color unless `NO_COLOR` is set; pass `--ansi` to fzf.
+pub fn picker_colorize(mode: ColorMode) -> bool {
+    match mode {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => std::env::var_os("NO_COLOR").is_none(),
+    }
+}
+
+pub struct PickerOptions {
+    pub use_absolute: bool,
+    pub color_mode: ColorMode,
+    /// `--print0`: end records with NUL and keep the path bytes exact.
+    pub null_terminated: bool,
+}
+
+/// `--picker`: `abs_path<TAB>time<TAB>type<TAB>name` per entry. The first
+/// field is for the machine; the others are padded so they line up and never
+/// contain a tab or newline, so widgets reading `--print0` records show them
+/// with `fzf --with-nth -3..` and take the path as everything before the last
+/// three tabs. Newline-terminated records escape a path holding a tab, CR,
+/// or LF, so `tab\there` printed that way is indistinguishable from a path
+/// with a real tab; only `--print0` keeps every path exact.
+pub fn render(
+    entries: &[FileEntry],
+    now: SystemTime,
+    base: &Path,
+    options: PickerOptions,
+) -> Result<()> {
+    colored::control::set_override(picker_colorize(options.color_mode));
+    let use_absolute = options.use_absolute;
+    let terminator = if options.null_terminated { '\0' } else { '\n' };
+
+    let times: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            if use_absolute {
+                absolute_time(entry.mtime)
+            } else {
+                relative_time(now, entry.mtime)
+            }
+        })
+        .collect();
+    let time_width = times.iter().map(|time| time.width()).max().unwrap_or(0);
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    for (entry, time) in entries.iter().zip(&times) {
+        if options.null_terminated {
+            writer.write_all(os_bytes(std::path::absolute(&entry.path)?.as_os_str()))?;
+        } else {
+            write_machine_path(&mut writer, &entry.path)?;
+        }
+        let time = pad_to_display_width(time, time_width, Alignment::Right);
+        let name = format_name(entry, base, QuotingStyle::Escape);
+        let theme = theme::current();
+        write!(
+            writer,
+            "\t{}\t{}\t{}{terminator}",
+            style_time_text(classify_bucket(now, entry.mtime), &time),
+            theme.label(entry.kind).paint(&format_type_label(entry)),
+            theme.name(entry.kind, &entry.name).paint(&name)
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The absolute path as exact bytes, so it can be handed back to other
+/// commands. Paths that would break the line or field layout fall back to
+/// the `escape` quoting style; `--print0` records never need to.
+fn write_machine_path(writer: &mut impl Write, path: &Path) -> Result<()> {
+    let absolute = std::path::absolute(path)?;
+    let bytes = os_bytes(absolute.as_os_str());
+    if bytes
+        .iter()
+        .any(|byte| matches!(byte, b'\t' | b'\n' | b'\r'))
+    {
+        write!(
+            writer,
+            "{}",
+            quote(absolute.as_os_str(), QuotingStyle::Escape)
+        )?;
+    } else {
+        writer.write_all(bytes)?;
+    }
+    Ok(())
+}
+
+/// `ftime preview PATH`: a compact metadata card for a fuzzy finder's
+/// preview window. `children` is the scan of a directory's contents.
+pub fn render_preview(
+    entry: &FileEntry,
+    children: Option<&ScanResult>,
+    now: SystemTime,
+    color_mode: ColorMode,
+) -> Result<()> {
+    colored::control::set_override(picker_colorize(color_mode));
+
+    let bucket = classify_bucket(now, entry.mtime);
+    let theme = theme::current();
+    let name = quote(&entry.name, QuotingStyle::Escape);
+    println!("{}", theme.name(entry.kind, &entry.name).paint(&name));
+
+    card_line("kind", entry.kind.key());
+    let size = match entry.size {
+        Some(size) if size >= 1024 => format!("{} ({size} bytes)", format_byte_size(size)),
+        _ => format_size(entry),
+    };
+    card_line("size", &size);
+    card_line(
+        "modified",
+        &format!(
+            "{}  {}",
+            style_time_text(bucket, &relative_time(now, entry.mtime)),
+            absolute_time(entry.mtime)
+        ),
+    );
+    card_line("bucket", &theme.header(bucket).paint(bucket.title()));
+    let absolute = std::path::absolute(&entry.path)?;
+    card_line("path", &quote(absolute.as_os_str(), QuotingStyle::Escape));
+    if let Some(target) = &entry.symlink_target {
+        card_line("target", &quote(target.as_os_str(), QuotingStyle::Escape));
+    }
+    if let Some(long) = &entry.long {
+        card_line(
+            "mode",
+            &format!("{}  {}:{}", format_mode(long.mode), long.owner, long.group),
+        );
+    }
+
+    let Some(children) = children else {
+        return Ok(());
+    };
+    card_line("entries", &children.entries.len().to_string());
+    let shown = &children.entries[..children.entries.len().min(PREVIEW_CHILDREN)];
+    let names: Vec<String> = shown
+        .iter()
+        .map(|child| format_name(child, &entry.path, QuotingStyle::Escape))
+        .collect();
+    let name_width = names.iter().map(|name| name.width()).max().unwrap_or(0);
+    for (index, (child, name)) in shown.iter().zip(&names).enumerate() {
+        let time = relative_time(now, child.mtime);
+        let line = format!(
+            "{}  {}",
+            theme
+                .name(child.kind, &child.name)
+                .paint(&pad_to_display_width(name, name_width, Alignment::Left)),
+            style_time_text(classify_bucket(now, child.mtime), &time)
+        );
+        card_line(if index == 0 { "newest" } else { "" }, &line);
+    }
+    Ok(())
+}
+
+fn card_line(label: &str, value: &str) {
+    println!(
+        "{}{value}",
+        pad_to_display_width(label, CARD_LABEL_WIDTH, Alignment::Left).dimmed()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn machine_path_is_absolute_and_escapes_only_layout_breaking_bytes() {
+        let mut out = Vec::new();
+        write_machine_path(&mut out, Path::new("/tmp/a b\\c.txt")).unwrap();
+        assert_eq!(out, b"/tmp/a b\\c.txt");
+
+        let mut out = Vec::new();
+        write_machine_path(&mut out, Path::new("/tmp/tab\there")).unwrap();
+        assert_eq!(out, b"/tmp/tab\\there");
+
+        let mut out = Vec::new();
+        write_machine_path(&mut out, Path::new("rel.txt")).unwrap();
+        assert!(Path::new(std::str::from_utf8(&out).unwrap()).is_absolute());
+    }
+}
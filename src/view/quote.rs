@@ -0,0 +1,154 @@
+use crate::util::bytes::escape_invalid_utf8;
+use clap::ValueEnum;
+use std::ffi::OsStr;
+
+/// How names are written by the human view and `--plain`, after coreutils `ls`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QuotingStyle {
+    /// Print names as they are; only invalid UTF-8 is escaped
+    Literal,
+    /// Quote for POSIX shells, using `$'...'` when control bytes are present
+    Shell,
+    /// Double-quote and C-escape every name
+    C,
+    /// C-escape control characters and backslashes without quoting
+    Escape,
+}
+
+pub fn quote(value: &OsStr, style: QuotingStyle) -> String {
+    match style {
+        QuotingStyle::Literal => escape_invalid_utf8(value),
+        QuotingStyle::Escape => c_escape(value, None),
+        QuotingStyle::C => format!("\"{}\"", c_escape(value, Some('"'))),
+        QuotingStyle::Shell => shell_quote(value),
+    }
+}
+
+fn shell_quote(value: &OsStr) -> String {
+    let Some(text) = value.to_str() else {
+        return format!("$'{}'", c_escape(value, Some('\'')));
+    };
+
+    if !text.is_empty() && text.chars().all(is_shell_safe) {
+        return text.to_string();
+    }
+
+    if text.chars().any(char::is_control) {
+        return format!("$'{}'", c_escape(value, Some('\'')));
+    }
+
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+fn is_shell_safe(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || "_@%+=:,./-".contains(ch)
+}
+
+/// Backslash-escape control characters, backslashes, `quote`, and invalid
+/// UTF-8 bytes so the result never carries raw terminal control codes.
+fn c_escape(value: &OsStr, quote: Option<char>) -> String {
+    let mut out = String::new();
+    for chunk in value.as_encoded_bytes().utf8_chunks() {
+        for ch in chunk.valid().chars() {
+            match ch {
+                '\\' => out.push_str(r"\\"),
+                ch if Some(ch) == quote => {
+                    out.push('\\');
+                    out.push(ch);
+                }
+                ch => push_escaped_control(&mut out, ch),
+            }
+        }
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out
+}
+
+/// Escape only the control characters of already-rendered text, e.g. a
+/// `--format` value whose backslashes and invalid bytes are escaped upstream.
+pub fn escape_control(text: &str) -> String {
+    if !text.chars().any(char::is_control) {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        push_escaped_control(&mut out, ch);
+    }
+    out
+}
+
+fn push_escaped_control(out: &mut String, ch: char) {
+    match ch {
+        '\n' => out.push_str(r"\n"),
+        '\t' => out.push_str(r"\t"),
+        '\r' => out.push_str(r"\r"),
+        ch if ch.is_control() && ch.is_ascii() => {
+            out.push_str(&format!("\\x{:02x}", ch as u32));
+        }
+        ch if ch.is_control() => out.push_str(&format!("\\u{:04x}", ch as u32)),
+        ch => out.push(ch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote_str(value: &str, style: QuotingStyle) -> String {
+        quote(OsStr::new(value), style)
+    }
+
+    #[test]
+    fn escape_neutralizes_control_characters() {
+        assert_eq!(
+            quote_str("evil\x1b[31m\nname", QuotingStyle::Escape),
+            r"evil\x1b[31m\nname"
+        );
+        assert_eq!(quote_str("a b\\c", QuotingStyle::Escape), r"a b\\c");
+        assert_eq!(
+            quote_str("next\u{85}line", QuotingStyle::Escape),
+            r"next\u0085line"
+        );
+    }
+
+    #[test]
+    fn shell_quotes_only_when_needed() {
+        assert_eq!(
+            quote_str("plain-name.txt", QuotingStyle::Shell),
+            "plain-name.txt"
+        );
+        assert_eq!(quote_str("with space", QuotingStyle::Shell), "'with space'");
+        assert_eq!(quote_str("it's", QuotingStyle::Shell), r"'it'\''s'");
+        assert_eq!(quote_str("tab\there", QuotingStyle::Shell), r"$'tab\there'");
+        assert_eq!(quote_str("", QuotingStyle::Shell), "''");
+    }
+
+    #[test]
+    fn c_style_always_quotes() {
+        assert_eq!(quote_str("say \"hi\"", QuotingStyle::C), r#""say \"hi\"""#);
+        assert_eq!(quote_str("plain", QuotingStyle::C), "\"plain\"");
+    }
+
+    #[test]
+    fn escape_control_leaves_backslashes_alone() {
+        assert_eq!(escape_control("two\nlines\x1b[0m"), r"two\nlines\x1b[0m");
+        assert_eq!(escape_control(r"a\\b"), r"a\\b");
+    }
+
+    #[test]
+    fn literal_keeps_control_characters() {
+        assert_eq!(quote_str("a\tb", QuotingStyle::Literal), "a\tb");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_bytes_survive_shell_quoting() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let name = OsStr::from_bytes(b"caf\xe9 it's");
+        assert_eq!(quote(name, QuotingStyle::Shell), r"$'caf\xe9 it\'s'");
+    }
+}
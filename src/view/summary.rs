@@ -0,0 +1,345 @@
+use crate::engine::{Bucketed, ScanOptions, ScanStats, bucketize, scan_children};
+use crate::model::{EntryKind, FileEntry, TimeBucket};
+use crate::view::quote::{QuotingStyle, quote};
+use crate::view::tty::{
+    Alignment, ColorMode, format_byte_size, pad_to_display_width, print_footers, should_colorize,
+};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+use std::time::SystemTime;
+use unicode_width::UnicodeWidthStr;
+
+/// Extension key for directories, which are grouped instead of split by name.
+pub const DIR_KEY: &str = "<dir>";
+/// Extension key for entries whose name has no extension.
+pub const NO_EXTENSION_KEY: &str = "(none)";
+/// Directory key for the entries that sit directly in the scanned path.
+pub const ROOT_KEY: &str = "./";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+impl Tally {
+    fn add(&mut self, entry: &FileEntry) {
+        self.count += 1;
+        self.bytes += entry.size.unwrap_or(0);
+    }
+
+    fn merge(&mut self, other: Tally) {
+        self.count += other.count;
+        self.bytes += other.bytes;
+    }
+}
+
+/// Counts and bytes for one table row, split by bucket in display order.
+pub struct SummaryRow {
+    pub key: String,
+    pub buckets: [(TimeBucket, Tally); 4],
+    /// A directory row whose contents could not be read; its counts are zero.
+    pub unreadable: bool,
+}
+
+impl SummaryRow {
+    fn new(key: String) -> Self {
+        Self {
+            key,
+            unreadable: false,
+            buckets: [
+                (TimeBucket::Active, Tally::default()),
+                (TimeBucket::Today, Tally::default()),
+                (TimeBucket::ThisWeek, Tally::default()),
+                (TimeBucket::History, Tally::default()),
+            ],
+        }
+    }
+
+    fn from_bucketed(key: String, bucketed: &Bucketed, keep: impl Fn(&FileEntry) -> bool) -> Self {
+        let mut row = Self::new(key);
+        for (index, (_, entries)) in bucketed.groups().into_iter().enumerate() {
+            for entry in entries.iter().filter(|entry| keep(entry)) {
+                row.buckets[index].1.add(entry);
+            }
+        }
+        row
+    }
+
+    pub fn total(&self) -> Tally {
+        let mut total = Tally::default();
+        for (_, tally) in self.buckets {
+            total.merge(tally);
+        }
+        total
+    }
+}
+
+/// `--summary` aggregates: the whole scan, each extension, and each
+/// top-level directory (whose direct children are read with the same filters).
+/// `stats` covers the scan and every directory read for the directory rows.
+pub struct Summary {
+    pub totals: SummaryRow,
+    pub extensions: Vec<SummaryRow>,
+    pub directories: Vec<SummaryRow>,
+    pub stats: ScanStats,
+}
+
+impl Summary {
+    pub fn build(
+        bucketed: &Bucketed,
+        stats: &ScanStats,
+        scan_opts: &ScanOptions,
+        now: SystemTime,
+    ) -> Self {
+        let totals = SummaryRow::from_bucketed("total".to_string(), bucketed, |_| true);
+
+        let mut extensions: Vec<SummaryRow> = Vec::new();
+        for (index, (_, entries)) in bucketed.groups().into_iter().enumerate() {
+            for entry in entries {
+                let key = extension_key(entry);
+                let position = match extensions.iter().position(|row| row.key == key) {
+                    Some(position) => position,
+                    None => {
+                        extensions.push(SummaryRow::new(key));
+                        extensions.len() - 1
+                    }
+                };
+                extensions[position].buckets[index].1.add(entry);
+            }
+        }
+        sort_rows(&mut extensions);
+
+        let mut directories = vec![SummaryRow::from_bucketed(
+            ROOT_KEY.to_string(),
+            bucketed,
+            |entry| !entry.is_dir(),
+        )];
+        let mut stats = stats.clone();
+        let mut children: Vec<SummaryRow> = bucketed
+            .groups()
+            .into_iter()
+            .flat_map(|(_, entries)| entries.iter())
+            .filter(|entry| entry.is_dir())
+            .map(|entry| {
+                let key = format!("{}/", quote(&entry.name, QuotingStyle::Escape));
+                match scan_children(&entry.path, scan_opts) {
+                    Ok(scan) => {
+                        stats.merge(&scan.stats);
+                        SummaryRow::from_bucketed(key, &bucketize(&scan.entries, now), |_| true)
+                    }
+                    Err(_) => {
+                        stats.skipped_unreadable += 1;
+                        SummaryRow {
+                            unreadable: true,
+                            ..SummaryRow::new(key)
+                        }
+                    }
+                }
+            })
+            .collect();
+        sort_rows(&mut children);
+        directories.extend(children);
+
+        Self {
+            totals,
+            extensions,
+            directories,
+            stats,
+        }
+    }
+}
+
+/// Busiest rows first; ties keep a stable alphabetical order.
+fn sort_rows(rows: &mut [SummaryRow]) {
+    rows.sort_by(|a, b| {
+        b.total()
+            .count
+            .cmp(&a.total().count)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+}
+
+fn extension_key(entry: &FileEntry) -> String {
+    if matches!(entry.kind, EntryKind::Dir | EntryKind::SymlinkDir) {
+        return DIR_KEY.to_string();
+    }
+
+    // Escaping keeps control characters and invalid UTF-8 out of the table;
+    // the escapes themselves are already lowercase.
+    Path::new(&entry.name)
+        .extension()
+        .map(|ext| format!(".{}", quote(ext, QuotingStyle::Escape).to_lowercase()))
+        .unwrap_or_else(|| NO_EXTENSION_KEY.to_string())
+}
+
+pub fn render(summary: &Summary, color_mode: ColorMode) -> Result<()> {
+    colored::control::set_override(should_colorize(color_mode));
+
+    let total = summary.totals.total();
+    println!(
+        "{}",
+        format!(
+            "Summary ({} entries, {})",
+            total.count,
+            format_byte_size(total.bytes)
+        )
+        .bold()
+    );
+    println!();
+
+    render_table("By extension", &summary.extensions, Some(&summary.totals));
+    render_table("By directory", &summary.directories, None);
+
+    print_footers(&summary.stats);
+    Ok(())
+}
+
+fn render_table(title: &str, rows: &[SummaryRow], totals: Option<&SummaryRow>) {
+    let key_width = rows
+        .iter()
+        .chain(totals)
+        .map(|row| row.key.width())
+        .chain([title.width().saturating_sub(2)])
+        .max()
+        .unwrap_or(0);
+
+    let mut header = vec![pad_to_display_width(title, key_width + 2, Alignment::Left)];
+    header.extend(
+        TABLE_HEADERS
+            .iter()
+            .map(|(label, width)| pad_to_display_width(label, *width, Alignment::Right)),
+    );
+    println!("{}", header.join("  ").bold());
+
+    for row in rows {
+        let cells = table_cells(row, key_width).join("  ");
+        if row.unreadable {
+            println!("  {cells}  {}", "[unreadable]".red());
+        } else {
+            println!("  {cells}");
+        }
+    }
+    if let Some(totals) = totals {
+        println!("  {}", table_cells(totals, key_width).join("  ").dimmed());
+    }
+    println!();
+}
+
+/// Right-aligned columns after the key: one count per bucket, then totals.
+const TABLE_HEADERS: [(&str, usize); 6] = [
+    ("Active", 6),
+    ("Today", 6),
+    ("This Week", 9),
+    ("History", 7),
+    ("Total", 6),
+    ("Size", 9),
+];
+
+fn table_cells(row: &SummaryRow, key_width: usize) -> Vec<String> {
+    let total = row.total();
+    let mut cells = vec![pad_to_display_width(&row.key, key_width, Alignment::Left)];
+    let values = row
+        .buckets
+        .iter()
+        .map(|(_, tally)| tally.count.to_string())
+        .chain([total.count.to_string(), format_byte_size(total.bytes)]);
+    for (value, (_, width)) in values.zip(TABLE_HEADERS) {
+        cells.push(pad_to_display_width(&value, width, Alignment::Right));
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn entry(name: &str, kind: EntryKind, age: u64, size: Option<u64>) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from(name),
+            name: OsString::from(name),
+            kind,
+            mtime: SystemTime::now() - Duration::from_secs(age),
+            size,
+            symlink_target: None,
+            hash: None,
+            long: None,
+        }
+    }
+
+    #[test]
+    fn extensions_reconcile_with_bucket_totals() {
+        let now = SystemTime::now();
+        let entries = [
+            entry("a.RS", EntryKind::File, 10, Some(100)),
+            entry("b.rs", EntryKind::File, 30 * 86_400, Some(50)),
+            entry("Makefile", EntryKind::File, 10, Some(7)),
+            entry("missing-dir", EntryKind::Dir, 10, None),
+        ];
+        let bucketed = bucketize(&entries, now);
+        let opts = ScanOptions {
+            dot_mode: crate::engine::DotMode::Default,
+            use_ignore: false,
+            ignore_patterns: Vec::new(),
+            local_ignore_patterns: Vec::new(),
+            ext_filter: None,
+            files_only: false,
+            show_hints: false,
+            since: None,
+            since_raw: None,
+            long: false,
+            follow_links: false,
+        };
+        let summary = Summary::build(&bucketed, &ScanStats::default(), &opts, now);
+
+        assert_eq!(
+            summary.totals.total(),
+            Tally {
+                count: 4,
+                bytes: 157
+            }
+        );
+        let keys: Vec<&str> = summary
+            .extensions
+            .iter()
+            .map(|row| row.key.as_str())
+            .collect();
+        assert_eq!(keys, [".rs", "(none)", "<dir>"]);
+        let rs = &summary.extensions[0];
+        assert_eq!(rs.buckets[0].1.count, 1);
+        assert_eq!(
+            rs.buckets[3].1,
+            Tally {
+                count: 1,
+                bytes: 50
+            }
+        );
+        let sum: usize = summary.extensions.iter().map(|row| row.total().count).sum();
+        assert_eq!(sum, bucketed.total());
+
+        assert_eq!(summary.directories[0].key, ROOT_KEY);
+        assert_eq!(summary.directories[0].total().count, 3);
+        assert_eq!(summary.directories[1].key, "missing-dir/");
+        assert_eq!(summary.directories[1].total(), Tally::default());
+        assert!(summary.directories[1].unreadable);
+        assert_eq!(summary.stats.skipped_unreadable, 1);
+    }
+
+    #[test]
+    fn extension_keys_escape_control_characters_and_invalid_utf8() {
+        let evil = entry("a.x\x1b[31mRED", EntryKind::File, 10, Some(1));
+        assert_eq!(extension_key(&evil), r".x\x1b[31mred");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let mut raw = entry("b", EntryKind::File, 10, Some(1));
+            raw.name = OsString::from(std::ffi::OsStr::from_bytes(b"b.\xff\xfe"));
+            assert_eq!(extension_key(&raw), r".\xff\xfe");
+        }
+    }
+}
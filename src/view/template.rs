@@ -0,0 +1,271 @@
+use crate::model::FileEntry;
+use crate::util::bytes::escape_invalid_utf8;
+use crate::util::time::{absolute_time, classify_bucket, relative_time, utc_rfc3339};
+use crate::view::quote::escape_control;
+use crate::view::tty::format_byte_size;
+use anyhow::{Result, bail};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Placeholder names accepted by `--format`, in the order they are documented.
+pub const PLACEHOLDERS: [&str; 20] = [
+    "path",
+    "name",
+    "ext",
+    "kind",
+    "size",
+    "size_human",
+    "mtime",
+    "mtime_local",
+    "mtime_unix",
+    "relative_time",
+    "bucket",
+    "is_dir",
+    "is_symlink",
+    "symlink_target",
+    "hash",
+    "mode",
+    "nlink",
+    "inode",
+    "owner",
+    "group",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(&'static str),
+}
+
+/// A parsed `--format` line template such as `{bucket}\t{path}\t{size}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template.
+    ///
+    /// `{name}` inserts a field, `{{` and `}}` insert literal braces, and
+    /// `\t`, `\n`, `\0`, and `\\` are unescaped so shell users do not need
+    /// `$'...'` quoting.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = input.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next() {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    Some('0') => literal.push('\0'),
+                    Some('\\') => literal.push('\\'),
+                    Some(other) => {
+                        literal.push('\\');
+                        literal.push(other);
+                    }
+                    None => literal.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => bail!(
+                    "invalid --format template: unmatched `}}`; use `}}}}` for a literal brace"
+                ),
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for next in chars.by_ref() {
+                        if next == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(next);
+                    }
+                    if !closed {
+                        bail!("invalid --format template: unclosed `{{{name}`");
+                    }
+                    let Some(field) = PLACEHOLDERS.iter().find(|field| **field == name) else {
+                        bail!(
+                            "invalid --format template: unknown placeholder `{{{name}}}` (supported: {})",
+                            PLACEHOLDERS.join(", ")
+                        );
+                    };
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(field));
+                }
+                other => literal.push(other),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    pub fn uses(&self, field: &str) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Field(name) if *name == field))
+    }
+
+    /// Field values have their control characters escaped, so a name with a
+    /// newline or tab can never split or forge a record; only the template's
+    /// own literals produce raw separators.
+    fn expand(&self, entry: &FileEntry, now: SystemTime, base: &Path) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Field(name) => {
+                    out.push_str(&escape_control(&field_value(name, entry, now, base)));
+                }
+            }
+        }
+        out
+    }
+}
+
+pub fn render(
+    entries: &[FileEntry],
+    now: SystemTime,
+    base: &Path,
+    template: &Template,
+) -> Result<()> {
+    let mut writer = BufWriter::new(std::io::stdout());
+    for entry in entries {
+        writeln!(writer, "{}", template.expand(entry, now, base))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Missing values (size of a directory, hash without `--hash`, ...) expand to
+/// an empty string.
+pub fn field_value(name: &str, entry: &FileEntry, now: SystemTime, base: &Path) -> String {
+    let long = entry.long.as_ref();
+    match name {
+        "path" => escape_invalid_utf8(entry.relative_path(base)),
+        "name" => escape_invalid_utf8(&entry.name),
+        "ext" => entry
+            .path
+            .extension()
+            .filter(|_| !entry.is_dir())
+            .map(escape_invalid_utf8)
+            .unwrap_or_default(),
+        "kind" => entry.kind.key().to_string(),
+        "size" => entry.size.map(|size| size.to_string()).unwrap_or_default(),
+        "size_human" => entry.size.map(format_byte_size).unwrap_or_default(),
+        "mtime" => utc_rfc3339(entry.mtime),
+        "mtime_local" => absolute_time(entry.mtime),
+        "mtime_unix" => entry
+            .mtime
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs().to_string())
+            .unwrap_or_else(|err| format!("-{}", err.duration().as_secs())),
+        "relative_time" => relative_time(now, entry.mtime),
+        "bucket" => classify_bucket(now, entry.mtime).key().to_string(),
+        "is_dir" => entry.is_dir().to_string(),
+        "is_symlink" => entry.is_symlink().to_string(),
+        "symlink_target" => entry
+            .symlink_target
+            .as_ref()
+            .map(|target| escape_invalid_utf8(target.as_os_str()))
+            .unwrap_or_default(),
+        "hash" => entry.hash.clone().unwrap_or_default(),
+        "mode" => long
+            .map(|long| format!("{:04o}", long.mode & 0o7777))
+            .unwrap_or_default(),
+        "nlink" => long.map(|long| long.nlink.to_string()).unwrap_or_default(),
+        "inode" => long.map(|long| long.inode.to_string()).unwrap_or_default(),
+        "owner" => long.map(|long| long.owner.clone()).unwrap_or_default(),
+        "group" => long.map(|long| long.group.clone()).unwrap_or_default(),
+        _ => unreachable!("placeholder names are validated by Template::parse"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EntryKind;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn entry() -> FileEntry {
+        FileEntry {
+            path: PathBuf::from("/work/src/main.rs"),
+            name: "main.rs".into(),
+            kind: EntryKind::File,
+            mtime: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            size: Some(2048),
+            symlink_target: None,
+            hash: None,
+            long: None,
+        }
+    }
+
+    #[test]
+    fn parse_unescapes_tabs_and_literal_braces() {
+        let template = Template::parse(r"{{{name}}}\t{size}").unwrap();
+        assert_eq!(
+            template.segments,
+            vec![
+                Segment::Literal("{".to_string()),
+                Segment::Field("name"),
+                Segment::Literal("}\t".to_string()),
+                Segment::Field("size"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_and_unclosed_placeholders() {
+        let err = Template::parse("{path} {colour}").unwrap_err().to_string();
+        assert!(err.contains("unknown placeholder `{colour}`"), "{err}");
+        assert!(err.contains("supported: path, name"), "{err}");
+
+        let err = Template::parse("{path").unwrap_err().to_string();
+        assert!(err.contains("unclosed `{path`"), "{err}");
+
+        let err = Template::parse("path}").unwrap_err().to_string();
+        assert!(err.contains("unmatched `}`"), "{err}");
+    }
+
+    #[test]
+    fn expand_fills_derived_fields_and_blanks_missing_values() {
+        let template =
+            Template::parse("{path}|{ext}|{kind}|{size_human}|{mtime_unix}|{hash}|{owner}")
+                .unwrap();
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_060);
+        assert_eq!(
+            template.expand(&entry(), now, Path::new("/work")),
+            "src/main.rs|rs|file|2.0 KiB|1700000000||"
+        );
+        assert!(template.uses("hash"));
+        assert!(!template.uses("bucket"));
+    }
+
+    #[test]
+    fn expand_escapes_control_characters_in_values() {
+        let mut entry = entry();
+        entry.path = PathBuf::from("/work/two\nlines\tdir\x1b[31m");
+        entry.name = "two\nlines\tdir\x1b[31m".into();
+        let template = Template::parse(r"{name}|{kind}\t{path}").unwrap();
+        let escaped = r"two\nlines\tdir\x1b[31m";
+        assert_eq!(
+            template.expand(&entry, SystemTime::now(), Path::new("/work")),
+            format!("{escaped}|file\t{escaped}")
+        );
+    }
+}
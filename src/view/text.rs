@@ -1,6 +1,9 @@
 use crate::model::FileEntry;
+use crate::util::bytes::os_bytes;
 use crate::util::time::{absolute_time, classify_bucket, relative_time};
+use crate::view::quote::{QuotingStyle, quote};
 use anyhow::Result;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::time::SystemTime;
 
@@ -9,20 +12,31 @@ pub fn render(
     now: SystemTime,
     base: &Path,
     use_absolute: bool,
+    show_hash: bool,
+    raw_bytes: bool,
+    quoting: QuotingStyle,
 ) -> Result<()> {
+    let mut writer = BufWriter::new(std::io::stdout());
     for entry in entries {
-        let path = entry
-            .path
-            .strip_prefix(base)
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|_| entry.name.clone());
+        let relative = entry.relative_path(base);
+        if raw_bytes {
+            writer.write_all(os_bytes(relative))?;
+        } else {
+            write!(writer, "{}", quote(relative, quoting))?;
+        }
         let time_str = if use_absolute {
             absolute_time(entry.mtime)
         } else {
             relative_time(now, entry.mtime)
         };
         let bucket = classify_bucket(now, entry.mtime);
-        println!("{path}\t{}\t{time_str}", bucket.key());
+        if show_hash {
+            let hash = entry.hash.as_deref().unwrap_or("-");
+            writeln!(writer, "\t{}\t{time_str}\t{hash}", bucket.key())?;
+        } else {
+            writeln!(writer, "\t{}\t{time_str}", bucket.key())?;
+        }
     }
+    writer.flush()?;
     Ok(())
 }
@@ -0,0 +1,695 @@
+use crate::model::{EntryKind, TimeBucket};
+use anyhow::{Context, Result, bail};
+use colored::{Color, ColoredString, Colorize};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Names accepted by `--theme` besides a theme file path.
+pub const BUILTIN_THEMES: [&str; 4] = ["default", "mono", "solarized", "high-contrast"];
+
+const BUCKET_KEYS: [&str; 4] = ["active", "today", "this_week", "history"];
+const KIND_KEYS: [&str; 8] = [
+    "file",
+    "dir",
+    "symlink",
+    "symlink_dir",
+    "broken_symlink",
+    "fifo",
+    "socket",
+    "device",
+];
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Make `theme` the one every human view paints with; the first call wins.
+pub fn install(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The installed theme, or `default` when none was installed.
+pub fn current() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
+
+/// Text attributes for one semantic role.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bold: bool,
+    pub dimmed: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl Style {
+    const fn fg(color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            bold: false,
+            dimmed: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    const fn plain() -> Self {
+        Self {
+            fg: None,
+            bold: false,
+            dimmed: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    const fn dimmed(mut self) -> Self {
+        self.dimmed = true;
+        self
+    }
+
+    const fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn paint(&self, text: &str) -> String {
+        if *self == Self::default() {
+            return text.to_string();
+        }
+
+        let mut painted = ColoredString::from(text);
+        if let Some(color) = self.fg {
+            painted = painted.color(color);
+        }
+        if self.bold {
+            painted = painted.bold();
+        }
+        if self.dimmed {
+            painted = painted.dimmed();
+        }
+        if self.italic {
+            painted = painted.italic();
+        }
+        if self.underline {
+            painted = painted.underline();
+        }
+        painted.to_string()
+    }
+}
+
+/// Styles for bucket headers, time tones, entry kinds, and child hints.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    header: [Style; 4],
+    time: [Style; 4],
+    skew: Style,
+    hint: Style,
+    name: [Style; 8],
+    label: [Style; 8],
+    ls_colors: Option<LsColors>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            header: [
+                Style::fg(Color::Green).bold(),
+                Style::plain().bold(),
+                Style::fg(Color::Cyan).bold(),
+                Style::plain(),
+            ],
+            time: [
+                Style::fg(Color::Green).bold(),
+                Style::plain(),
+                Style::fg(Color::Cyan),
+                Style::plain(),
+            ],
+            skew: Style::fg(Color::Yellow).bold(),
+            hint: Style::plain(),
+            name: [
+                Style::plain(),
+                Style::fg(Color::Blue).bold(),
+                Style::fg(Color::Magenta).underline(),
+                Style::fg(Color::Magenta).bold().underline(),
+                Style::fg(Color::Red).underline(),
+                Style::fg(Color::Yellow),
+                Style::fg(Color::BrightMagenta).bold(),
+                Style::fg(Color::Yellow).bold(),
+            ],
+            label: [
+                Style::plain().dimmed(),
+                Style::fg(Color::Blue).bold(),
+                Style::fg(Color::Magenta),
+                Style::fg(Color::Magenta).bold(),
+                Style::fg(Color::Red).bold(),
+                Style::fg(Color::Yellow),
+                Style::fg(Color::BrightMagenta),
+                Style::fg(Color::Yellow),
+            ],
+            ls_colors: None,
+        }
+    }
+}
+
+impl Theme {
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "mono" => Some(Self::mono()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Attributes only, for terminals or readers that do without color.
+    fn mono() -> Self {
+        let bold = Style::plain().bold();
+        let underline = Style::plain().underline();
+        Self {
+            header: [bold, bold, bold, Style::plain()],
+            time: [
+                bold,
+                Style::plain(),
+                Style::plain(),
+                Style::plain().dimmed(),
+            ],
+            skew: bold.underline(),
+            hint: Style::plain().dimmed(),
+            name: [
+                Style::plain(),
+                bold,
+                underline,
+                bold.underline(),
+                underline.dimmed(),
+                Style::plain(),
+                bold,
+                bold,
+            ],
+            label: [
+                Style::plain().dimmed(),
+                bold,
+                Style::plain(),
+                bold,
+                bold,
+                Style::plain(),
+                Style::plain(),
+                Style::plain(),
+            ],
+            ls_colors: None,
+        }
+    }
+
+    fn solarized() -> Self {
+        const fn rgb(r: u8, g: u8, b: u8) -> Style {
+            Style::fg(Color::TrueColor { r, g, b })
+        }
+        let yellow = rgb(0xb5, 0x89, 0x00);
+        let orange = rgb(0xcb, 0x4b, 0x16);
+        let red = rgb(0xdc, 0x32, 0x2f);
+        let magenta = rgb(0xd3, 0x36, 0x82);
+        let violet = rgb(0x6c, 0x71, 0xc4);
+        let blue = rgb(0x26, 0x8b, 0xd2);
+        let cyan = rgb(0x2a, 0xa1, 0x98);
+        let green = rgb(0x85, 0x99, 0x00);
+        let base01 = rgb(0x58, 0x6e, 0x75);
+        let base1 = rgb(0x93, 0xa1, 0xa1);
+
+        Self {
+            header: [green.bold(), base1.bold(), cyan.bold(), base01],
+            time: [green.bold(), base1, cyan, base01],
+            skew: orange.bold(),
+            hint: violet,
+            name: [
+                Style::plain(),
+                blue.bold(),
+                violet.underline(),
+                violet.bold().underline(),
+                red.underline(),
+                yellow,
+                magenta.bold(),
+                orange.bold(),
+            ],
+            label: [
+                base01,
+                blue.bold(),
+                violet,
+                violet.bold(),
+                red.bold(),
+                yellow,
+                magenta,
+                orange,
+            ],
+            ls_colors: None,
+        }
+    }
+
+    /// Bright, bold colors for low-contrast terminals and projectors.
+    fn high_contrast() -> Self {
+        let style = |color| Style::fg(color).bold();
+        Self {
+            header: [
+                style(Color::BrightGreen),
+                style(Color::BrightWhite),
+                style(Color::BrightCyan),
+                style(Color::White),
+            ],
+            time: [
+                style(Color::BrightGreen),
+                style(Color::BrightWhite),
+                style(Color::BrightCyan),
+                Style::fg(Color::White),
+            ],
+            skew: style(Color::BrightYellow).underline(),
+            hint: style(Color::BrightYellow),
+            name: [
+                style(Color::BrightWhite),
+                style(Color::BrightBlue),
+                style(Color::BrightMagenta).underline(),
+                style(Color::BrightMagenta).underline(),
+                style(Color::BrightRed).underline(),
+                style(Color::BrightYellow),
+                style(Color::BrightMagenta),
+                style(Color::BrightYellow),
+            ],
+            label: [
+                Style::fg(Color::White),
+                style(Color::BrightBlue),
+                style(Color::BrightMagenta),
+                style(Color::BrightMagenta),
+                style(Color::BrightRed),
+                style(Color::BrightYellow),
+                style(Color::BrightMagenta),
+                style(Color::BrightYellow),
+            ],
+            ls_colors: None,
+        }
+    }
+
+    /// Resolve `--theme` (or `FTIME_THEME`): a built-in name or a theme file.
+    pub fn load(source: &str, spec: &str) -> Result<Self> {
+        if let Some(theme) = Self::builtin(spec) {
+            return Ok(theme);
+        }
+
+        let path = Path::new(spec);
+        if !path.is_file() {
+            bail!(
+                "invalid value for {source}: `{spec}` is neither a built-in theme ({}) nor a theme file",
+                BUILTIN_THEMES.join(", ")
+            );
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read theme file {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("invalid theme file {}", path.display()))
+    }
+
+    /// Parse `role = style` lines; `base = <built-in>` picks the starting
+    /// theme and `ls_colors = true` lets `LS_COLORS` style names.
+    pub fn parse(text: &str) -> Result<Self> {
+        let lines: Vec<(usize, &str, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(number, line)| {
+                let (key, value) = line
+                    .split_once('=')
+                    .with_context(|| format!("line {number}: expected `role = style`"))?;
+                Ok((number, key.trim(), value.trim()))
+            })
+            .collect::<Result<_>>()?;
+
+        let mut theme = Self::default();
+        for (number, key, value) in &lines {
+            if *key == "base" {
+                theme = Self::builtin(value).with_context(|| {
+                    format!(
+                        "line {number}: unknown base theme `{value}` (supported: {})",
+                        BUILTIN_THEMES.join(", ")
+                    )
+                })?;
+            }
+        }
+
+        for (number, key, value) in lines {
+            match key {
+                "base" => {}
+                "ls_colors" => {
+                    theme.ls_colors = match value {
+                        "true" => Some(LsColors::from_env()),
+                        "false" => None,
+                        _ => bail!("line {number}: ls_colors must be true or false"),
+                    }
+                }
+                _ => {
+                    let style = parse_style(value).with_context(|| format!("line {number}"))?;
+                    *theme
+                        .role_mut(key)
+                        .with_context(|| format!("line {number}: unknown role `{key}`"))? = style;
+                }
+            }
+        }
+
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, key: &str) -> Option<&mut Style> {
+        match key {
+            "skew" => return Some(&mut self.skew),
+            "hint" => return Some(&mut self.hint),
+            _ => {}
+        }
+
+        let (group, role) = key.split_once('.')?;
+        let (styles, keys): (&mut [Style], &[&str]) = match group {
+            "header" => (&mut self.header, &BUCKET_KEYS),
+            "time" => (&mut self.time, &BUCKET_KEYS),
+            "name" => (&mut self.name, &KIND_KEYS),
+            "label" => (&mut self.label, &KIND_KEYS),
+            _ => return None,
+        };
+        let index = keys.iter().position(|candidate| *candidate == role)?;
+        Some(&mut styles[index])
+    }
+
+    pub fn header(&self, bucket: TimeBucket) -> Style {
+        self.header[bucket_index(bucket)]
+    }
+
+    pub fn time(&self, bucket: TimeBucket) -> Style {
+        self.time[bucket_index(bucket)]
+    }
+
+    pub fn skew(&self) -> Style {
+        self.skew
+    }
+
+    pub fn hint(&self) -> Style {
+        self.hint
+    }
+
+    pub fn label(&self, kind: EntryKind) -> Style {
+        self.label[kind_index(kind)]
+    }
+
+    /// Name style, preferring `LS_COLORS` when the theme enables it.
+    pub fn name(&self, kind: EntryKind, name: &OsStr) -> Style {
+        self.ls_colors
+            .as_ref()
+            .and_then(|ls_colors| ls_colors.style(kind, name))
+            .unwrap_or(self.name[kind_index(kind)])
+    }
+}
+
+fn bucket_index(bucket: TimeBucket) -> usize {
+    match bucket {
+        TimeBucket::Active => 0,
+        TimeBucket::Today => 1,
+        TimeBucket::ThisWeek => 2,
+        TimeBucket::History => 3,
+    }
+}
+
+fn kind_index(kind: EntryKind) -> usize {
+    match kind {
+        EntryKind::File => 0,
+        EntryKind::Dir => 1,
+        EntryKind::Symlink => 2,
+        EntryKind::SymlinkDir => 3,
+        EntryKind::BrokenSymlink => 4,
+        EntryKind::Fifo => 5,
+        EntryKind::Socket => 6,
+        EntryKind::BlockDevice | EntryKind::CharDevice => 7,
+    }
+}
+
+/// Parse a style such as `bold bright_green`, `underline #268bd2`, or `none`.
+fn parse_style(value: &str) -> Result<Style> {
+    let mut style = Style::default();
+    for word in value.split_whitespace() {
+        match word {
+            "none" | "plain" => {}
+            "bold" => style.bold = true,
+            "dim" | "dimmed" => style.dimmed = true,
+            "italic" => style.italic = true,
+            "underline" => style.underline = true,
+            _ => style.fg = Some(parse_color(word)?),
+        }
+    }
+    Ok(style)
+}
+
+fn parse_color(word: &str) -> Result<Color> {
+    if let Some(hex) = word.strip_prefix('#')
+        && hex.len() == 6
+        && let Ok(value) = u32::from_str_radix(hex, 16)
+    {
+        let [_, r, g, b] = value.to_be_bytes();
+        return Ok(Color::TrueColor { r, g, b });
+    }
+
+    Color::from_str(&word.replace('_', " ")).map_err(|()| {
+        anyhow::anyhow!(
+            "unknown style `{word}` (use bold, dim, italic, underline, a color name such as bright_green, or #rrggbb)"
+        )
+    })
+}
+
+/// Name styles from the `LS_COLORS` environment variable (`di=01;34:*.rs=33`).
+#[derive(Debug, Clone, Default)]
+struct LsColors {
+    kinds: HashMap<String, Style>,
+    extensions: HashMap<String, Style>,
+}
+
+impl LsColors {
+    fn from_env() -> Self {
+        std::env::var("LS_COLORS")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    fn parse(value: &str) -> Self {
+        let mut ls_colors = Self::default();
+        for item in value.split(':') {
+            let Some((key, codes)) = item.split_once('=') else {
+                continue;
+            };
+            let Some(style) = parse_sgr(codes) else {
+                continue;
+            };
+            match key.strip_prefix("*.") {
+                Some(ext) => {
+                    ls_colors.extensions.insert(ext.to_lowercase(), style);
+                }
+                None => {
+                    ls_colors.kinds.insert(key.to_string(), style);
+                }
+            }
+        }
+        ls_colors
+    }
+
+    /// `name` is the entry's own file name, not the quoted or padded text
+    /// shown for it, so `'my file.rs'` still matches `*.rs`.
+    fn style(&self, kind: EntryKind, name: &OsStr) -> Option<Style> {
+        let code = match kind {
+            EntryKind::File => {
+                let by_extension = Path::new(name)
+                    .extension()
+                    .and_then(|ext| self.extensions.get(&ext.to_string_lossy().to_lowercase()));
+                if by_extension.is_some() {
+                    return by_extension.copied();
+                }
+                "fi"
+            }
+            EntryKind::Dir => "di",
+            EntryKind::Symlink | EntryKind::SymlinkDir => "ln",
+            EntryKind::BrokenSymlink => "or",
+            EntryKind::Fifo => "pi",
+            EntryKind::Socket => "so",
+            EntryKind::BlockDevice => "bd",
+            EntryKind::CharDevice => "cd",
+        };
+        self.kinds.get(code).copied()
+    }
+}
+
+/// Foreground and attributes from an SGR sequence; backgrounds are skipped.
+fn parse_sgr(codes: &str) -> Option<Style> {
+    let codes: Vec<u8> = codes
+        .split(';')
+        .map(|code| code.parse().ok())
+        .collect::<Option<_>>()?;
+
+    let mut style = Style::default();
+    let mut index = 0;
+    while index < codes.len() {
+        match codes[index] {
+            0 => style = Style::default(),
+            1 => style.bold = true,
+            2 => style.dimmed = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            code @ 30..=37 => style.fg = Some(ANSI_COLORS[usize::from(code - 30)]),
+            code @ 90..=97 => style.fg = Some(ANSI_COLORS[usize::from(code - 90) + 8]),
+            code @ (38 | 48) => {
+                let (color, used) = extended_color(&codes[index + 1..]);
+                if code == 38 && color.is_some() {
+                    style.fg = color;
+                }
+                index += used;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    Some(style)
+}
+
+/// The color after a `38`/`48` code and how many codes it used.
+fn extended_color(rest: &[u8]) -> (Option<Color>, usize) {
+    match rest {
+        [5, code, ..] => (Some(ansi256(*code)), 2),
+        [2, r, g, b, ..] => (
+            Some(Color::TrueColor {
+                r: *r,
+                g: *g,
+                b: *b,
+            }),
+            4,
+        ),
+        _ => (None, rest.len()),
+    }
+}
+
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// The xterm 256-color palette as colors `colored` can print.
+fn ansi256(code: u8) -> Color {
+    match code {
+        0..=15 => ANSI_COLORS[usize::from(code)],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = code - 16;
+            Color::TrueColor {
+                r: level(index / 36),
+                g: level(index / 6 % 6),
+                b: level(index % 6),
+            }
+        }
+        232..=255 => {
+            let gray = 8 + (code - 232) * 10;
+            Color::TrueColor {
+                r: gray,
+                g: gray,
+                b: gray,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn theme_files_start_from_a_base_and_override_roles() {
+        let theme = Theme::parse(
+            "# retro colors\nheader.active = underline #268bd2\nbase = mono\nname.dir = bright_blue\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            theme.header(TimeBucket::Active),
+            Style::fg(Color::TrueColor {
+                r: 0x26,
+                g: 0x8b,
+                b: 0xd2
+            })
+            .underline()
+        );
+        assert_eq!(theme.header(TimeBucket::Today), Style::plain().bold());
+        assert_eq!(
+            theme.name(EntryKind::Dir, OsStr::new("src")),
+            Style::fg(Color::BrightBlue)
+        );
+    }
+
+    #[test]
+    fn theme_files_report_the_offending_line() {
+        let err = format!(
+            "{:#}",
+            Theme::parse("hint = dim\nname.pipe = red").unwrap_err()
+        );
+        assert!(err.contains("line 2: unknown role `name.pipe`"), "{err}");
+
+        let err = format!("{:#}", Theme::parse("skew = sparkly").unwrap_err());
+        assert!(err.contains("line 1: unknown style `sparkly`"), "{err}");
+    }
+
+    #[test]
+    fn ls_colors_style_extensions_before_kinds() {
+        let ls_colors = LsColors::parse("di=01;34:fi=0:*.RS=38;5;208:*.md=4;92:bd=40;33;01");
+
+        assert_eq!(
+            ls_colors.style(EntryKind::File, OsStr::new("main.rs")),
+            Some(Style::fg(Color::TrueColor {
+                r: 255,
+                g: 135,
+                b: 0
+            }))
+        );
+        assert_eq!(
+            ls_colors.style(EntryKind::File, OsStr::new("my notes.md")),
+            Some(Style::fg(Color::BrightGreen).underline())
+        );
+        assert_eq!(
+            ls_colors.style(EntryKind::File, OsStr::new("Makefile")),
+            Some(Style::default())
+        );
+        assert_eq!(
+            ls_colors.style(EntryKind::Dir, OsStr::new("src")),
+            Some(Style::fg(Color::Blue).bold())
+        );
+        assert_eq!(
+            ls_colors.style(EntryKind::BlockDevice, OsStr::new("sda")),
+            Some(Style::fg(Color::Yellow).bold())
+        );
+        assert_eq!(ls_colors.style(EntryKind::Fifo, OsStr::new("pipe")), None);
+    }
+
+    #[test]
+    fn builtin_themes_are_all_loadable() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some(), "{name}");
+        }
+        assert!(Theme::builtin("dracula").is_none());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::model::FileEntry;
+use crate::util::time::{classify_bucket, start_of_day, start_of_hour};
+use crate::view::tty::{ColorMode, should_colorize, style_time_text};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+use colored::Colorize;
+use std::io::{BufWriter, Write};
+use std::time::{Duration, SystemTime};
+
+/// Width of a `--timeline` bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimelineBin {
+    Hour,
+    Day,
+}
+
+impl TimelineBin {
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+
+    /// Number of bins `--timeline` shows, ending with the one that contains now.
+    pub fn default_span(self) -> usize {
+        match self {
+            Self::Hour => 24,
+            Self::Day => 30,
+        }
+    }
+
+    fn floor(self, ts: SystemTime) -> SystemTime {
+        match self {
+            Self::Hour => start_of_hour(ts),
+            Self::Day => start_of_day(ts),
+        }
+    }
+}
+
+/// Cell width of the longest bar in the human view.
+const MAX_BAR_WIDTH: usize = 40;
+const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+pub struct TimelineBinCount {
+    pub start: SystemTime,
+    pub count: usize,
+}
+
+/// Modification counts per hour or day, oldest bin first.
+pub struct Timeline {
+    pub bin: TimelineBin,
+    pub bins: Vec<TimelineBinCount>,
+    /// Entries older than the first bin.
+    pub earlier: usize,
+    pub total: usize,
+}
+
+impl Timeline {
+    /// Bin `entries` into the `span` local hours or days ending now; entries
+    /// from the future (clock skew) land in the newest bin.
+    pub fn build(entries: &[FileEntry], now: SystemTime, bin: TimelineBin, span: usize) -> Self {
+        let mut starts = vec![bin.floor(now)];
+        while starts.len() < span {
+            let previous = starts[starts.len() - 1] - Duration::from_secs(1);
+            starts.push(bin.floor(previous));
+        }
+        starts.reverse();
+
+        let mut bins: Vec<TimelineBinCount> = starts
+            .into_iter()
+            .map(|start| TimelineBinCount { start, count: 0 })
+            .collect();
+        let mut earlier = 0;
+        for entry in entries {
+            match bins.iter().rposition(|bin| entry.mtime >= bin.start) {
+                Some(index) => bins[index].count += 1,
+                None => earlier += 1,
+            }
+        }
+
+        Self {
+            bin,
+            bins,
+            earlier,
+            total: entries.len(),
+        }
+    }
+}
+
+pub fn render(timeline: &Timeline, now: SystemTime, color_mode: ColorMode) -> Result<()> {
+    colored::control::set_override(should_colorize(color_mode));
+
+    let mut writer = BufWriter::new(std::io::stdout());
+    let (unit, label_format) = match timeline.bin {
+        TimelineBin::Hour => ("hours", "%m-%d %H:00"),
+        TimelineBin::Day => ("days", "%Y-%m-%d %a"),
+    };
+    writeln!(
+        writer,
+        "{}",
+        format!("Timeline (last {} {unit})", timeline.bins.len()).bold()
+    )?;
+
+    let max_count = timeline.bins.iter().map(|bin| bin.count).max().unwrap_or(0);
+    for bin in &timeline.bins {
+        let label = DateTime::<Local>::from(bin.start).format(label_format);
+        let bar = bar(bin.count, max_count, MAX_BAR_WIDTH);
+        let padding = " ".repeat(MAX_BAR_WIDTH - bar.chars().count());
+        let bar = style_time_text(classify_bucket(now, bin.start), &bar);
+        let count = if bin.count == 0 {
+            "0".dimmed().to_string()
+        } else {
+            bin.count.to_string()
+        };
+        writeln!(writer, "  {label}  │{bar}{padding} {count}")?;
+    }
+
+    if timeline.earlier > 0 {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{}",
+            format!("{} earlier entries not shown", timeline.earlier).dimmed()
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// A bar of full blocks plus one eighth-block remainder, scaled so that
+/// `max` fills `width` cells.
+fn bar(count: usize, max: usize, width: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    let eighths = count * width * 8 / max;
+    format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EntryKind;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn entry(mtime: SystemTime) -> FileEntry {
+        FileEntry {
+            path: PathBuf::from("x"),
+            name: OsString::from("x"),
+            kind: EntryKind::File,
+            mtime,
+            size: Some(1),
+            symlink_target: None,
+            hash: None,
+            long: None,
+        }
+    }
+
+    #[test]
+    fn build_counts_entries_per_bin_and_reports_older_ones() {
+        let now = SystemTime::now();
+        let entries = [
+            entry(now),
+            entry(now + Duration::from_secs(120)),
+            entry(start_of_hour(now) - Duration::from_secs(1)),
+            entry(now - Duration::from_secs(48 * 3600)),
+        ];
+        let timeline = Timeline::build(&entries, now, TimelineBin::Hour, 24);
+
+        assert_eq!(timeline.bins.len(), 24);
+        assert_eq!(timeline.bins[23].count, 2);
+        assert_eq!(timeline.bins[22].count, 1);
+        assert_eq!(timeline.earlier, 1);
+        assert_eq!(timeline.total, 4);
+    }
+
+    #[test]
+    fn bar_scales_to_width_with_eighth_blocks() {
+        assert_eq!(bar(4, 4, 2), "██");
+        assert_eq!(bar(1, 4, 2), "▌");
+        assert_eq!(bar(0, 4, 2), "");
+        assert_eq!(bar(0, 0, 2), "");
+    }
+}
@@ -0,0 +1,133 @@
+use crate::engine::{ScanOptions, ScanStats, scan_children};
+use crate::model::FileEntry;
+use crate::util::time::{classify_bucket, relative_time};
+use crate::view::quote::{QuotingStyle, quote};
+use crate::view::tty::{ColorMode, print_footers, should_colorize, style_time_text};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+use std::time::SystemTime;
+
+pub struct TreeOptions<'a> {
+    pub now: SystemTime,
+    pub base: &'a Path,
+    /// Branches with nothing modified at or after this time are collapsed.
+    pub collapse_before: SystemTime,
+    pub quoting: QuotingStyle,
+    pub color_mode: ColorMode,
+    pub scan_opts: &'a ScanOptions,
+}
+
+struct Node<'a> {
+    entry: &'a FileEntry,
+    children: Vec<FileEntry>,
+    /// A directory whose contents could not be read.
+    unreadable: bool,
+    newest: SystemTime,
+}
+
+pub fn render(entries: &[FileEntry], stats: &ScanStats, options: TreeOptions<'_>) -> Result<()> {
+    colored::control::set_override(should_colorize(options.color_mode));
+
+    if entries.is_empty() {
+        println!("No matching entries");
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        quote(options.base.as_os_str(), options.quoting).bold()
+    );
+    // Expanded directories report their skipped entries in the same footers.
+    let mut stats = stats.clone();
+    let nodes = entries
+        .iter()
+        .map(|entry| {
+            let (children, unreadable) = if entry.is_dir() {
+                match scan_children(&entry.path, options.scan_opts) {
+                    Ok(scan) => {
+                        stats.merge(&scan.stats);
+                        (scan.entries, false)
+                    }
+                    Err(_) => {
+                        stats.skipped_unreadable += 1;
+                        (Vec::new(), true)
+                    }
+                }
+            } else {
+                (Vec::new(), false)
+            };
+            let newest = children
+                .iter()
+                .map(|child| child.mtime)
+                .fold(entry.mtime, SystemTime::max);
+            Node {
+                entry,
+                children,
+                unreadable,
+                newest,
+            }
+        })
+        .collect::<Vec<_>>();
+    render_level(&nodes, "", &options);
+    println!();
+
+    print_footers(&stats);
+    Ok(())
+}
+
+/// Print recent nodes with `├──`/`└──` branches and fold the rest into one
+/// `… N older entries` line.
+fn render_level(nodes: &[Node<'_>], prefix: &str, options: &TreeOptions<'_>) {
+    let (recent, older): (Vec<&Node<'_>>, Vec<&Node<'_>>) = nodes
+        .iter()
+        .partition(|node| node.newest >= options.collapse_before);
+
+    for (index, node) in recent.iter().enumerate() {
+        let is_last = index + 1 == recent.len() && older.is_empty();
+        let branch = if is_last { "└── " } else { "├── " };
+        let label = node_label(node.entry, options);
+        if node.unreadable {
+            println!("{prefix}{branch}{label}  {}", "[unreadable]".red());
+        } else {
+            println!("{prefix}{branch}{label}");
+        }
+
+        if node.children.is_empty() {
+            continue;
+        }
+        let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+        let children: Vec<Node<'_>> = node
+            .children
+            .iter()
+            .map(|child| Node {
+                entry: child,
+                children: Vec::new(),
+                unreadable: false,
+                newest: child.mtime,
+            })
+            .collect();
+        render_level(&children, &child_prefix, options);
+    }
+
+    if !older.is_empty() {
+        let summary = format!("… {} older entries", older.len());
+        println!("{prefix}└── {}", summary.dimmed());
+    }
+}
+
+fn node_label(entry: &FileEntry, options: &TreeOptions<'_>) -> String {
+    let bucket = classify_bucket(options.now, entry.mtime);
+    let name = quote(&entry.name, options.quoting);
+    let name = if entry.is_dir() {
+        format!("{name}/")
+    } else {
+        name
+    };
+
+    format!(
+        "{}  {}",
+        style_time_text(bucket, &name),
+        relative_time(options.now, entry.mtime).dimmed()
+    )
+}
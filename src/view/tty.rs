@@ -1,22 +1,32 @@
 use crate::engine::{Bucketed, ScanOptions, ScanStats, dir_child_activity_hint};
-use crate::model::{ChildActivityHint, EntryKind, FileEntry, TimeBucket};
+use crate::model::{ChildActivityHint, EntryKind, FileEntry, LongMetadata, TimeBucket};
 use crate::util::time::{absolute_time, relative_time};
-#[cfg(feature = "icons")]
-use crate::view::icon::NerdIconProvider;
-use crate::view::icon::{DefaultIconProvider, IconProvider};
+use crate::view::hyperlink::{self, Linker};
+use crate::view::icon::{self, IconSet};
+use crate::view::limit::PreviewLimits;
+use crate::view::quote::{QuotingStyle, quote};
+use crate::view::theme;
+use crate::view::timeline::{Timeline, TimelineBin};
 use anyhow::Result;
+use chrono::{DateTime, Datelike, Local};
 use clap::ValueEnum;
 use colored::Colorize;
+use std::ffi::OsString;
 use std::io::IsTerminal;
 use std::path::Path;
 use std::time::SystemTime;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-const ACTIVE_LIMIT: usize = 20;
-const TODAY_LIMIT: usize = 20;
-const WEEK_LIMIT: usize = 20;
-const HISTORY_LIMIT: usize = 5;
-const MAX_NAME_DISPLAY_WIDTH: usize = 28;
+/// Name column cap when the terminal width is unknown.
+const DEFAULT_NAME_DISPLAY_WIDTH: usize = 28;
+/// Line width assumed by `--grid` when the terminal width is unknown.
+const DEFAULT_GRID_WIDTH: usize = 80;
+/// Below this name width, low-priority columns are dropped instead.
+const MIN_NAME_DISPLAY_WIDTH: usize = 16;
+const HEATMAP_WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const HEATMAP_GLYPHS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+const COLUMN_GAP: usize = 2;
+const ROW_INDENT: usize = 2;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum ColorMode {
@@ -25,14 +35,35 @@ pub enum ColorMode {
     Never,
 }
 
+/// How over-long names are shortened to fit the name column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TruncateMode {
+    /// Cut the end and keep the extension: `long-report-dr~.pdf`
+    Suffix,
+    /// Cut the middle and keep both ends: `long-re~draft-3.pdf`
+    Middle,
+}
+
 #[derive(Clone, Copy)]
 pub struct RenderOptions<'a> {
     pub now: SystemTime,
     pub base: &'a Path,
-    pub show_all_history: bool,
-    pub use_icons: bool,
+    /// Rows each bucket previews.
+    pub limits: PreviewLimits,
+    /// Show only the newest N entries overall (`--limit N`).
+    pub limit: Option<usize>,
+    /// Icons for bucket headers and entries; `None` shows none.
+    pub icons: Option<IconSet>,
+    /// Turns names into OSC 8 `file://` links when set (`--hyperlink`).
+    pub hyperlinks: Option<&'a Linker>,
     pub use_absolute: bool,
+    pub long: bool,
     pub color_mode: ColorMode,
+    pub quoting: QuotingStyle,
+    pub truncate: TruncateMode,
+    pub grid: bool,
+    /// Terminal width in cells; `None` keeps the fixed default name width.
+    pub width: Option<usize>,
     pub scan_opts: &'a ScanOptions,
 }
 
@@ -49,11 +80,26 @@ enum TimeTone {
 struct RenderedRow {
     bucket: TimeBucket,
     type_label: String,
+    icon: &'static str,
+    /// The display text: quoted, relative to the base, `/` for directories.
     name: String,
+    /// The entry's own file name, which `LS_COLORS` patterns match.
+    file_name: OsString,
     size: String,
     time: String,
     suffix: String,
     kind: EntryKind,
+    link: Option<String>,
+    long: Option<LongColumns>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct LongColumns {
+    mode: String,
+    links: String,
+    owner: String,
+    group: String,
+    inode: String,
 }
 
 #[derive(Debug, Clone)]
@@ -63,12 +109,50 @@ struct RenderedBucket {
     rows: Vec<RenderedRow>,
 }
 
+/// Columns that may be dropped on narrow terminals, lowest priority first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Inode,
+    Group,
+    Links,
+    Owner,
+    Mode,
+    Size,
+}
+
+const DROP_ORDER: [Column; 6] = [
+    Column::Inode,
+    Column::Group,
+    Column::Links,
+    Column::Owner,
+    Column::Mode,
+    Column::Size,
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Layout {
+    widths: ColumnWidths,
+    dropped: Vec<Column>,
+}
+
+impl Layout {
+    fn shows(&self, column: Column) -> bool {
+        !self.dropped.contains(&column)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 struct ColumnWidths {
     type_label: usize,
+    icon: usize,
     name: usize,
     size: usize,
     time: usize,
+    mode: usize,
+    links: usize,
+    owner: usize,
+    group: usize,
+    inode: usize,
 }
 
 pub fn render(buckets: &Bucketed, stats: &ScanStats, options: RenderOptions<'_>) -> Result<()> {
@@ -83,68 +167,86 @@ pub fn render(buckets: &Bucketed, stats: &ScanStats, options: RenderOptions<'_>)
     }
 
     let mut rendered = Vec::new();
-    push_rendered_bucket(
-        &mut rendered,
-        buckets.active.as_slice(),
-        TimeBucket::Active,
-        ACTIVE_LIMIT,
-        false,
-        options,
-    );
-    push_rendered_bucket(
-        &mut rendered,
-        buckets.today.as_slice(),
-        TimeBucket::Today,
-        TODAY_LIMIT,
-        false,
-        options,
-    );
-    push_rendered_bucket(
-        &mut rendered,
-        buckets.week.as_slice(),
-        TimeBucket::ThisWeek,
-        WEEK_LIMIT,
-        false,
-        options,
-    );
-    push_rendered_bucket(
-        &mut rendered,
-        buckets.history.as_slice(),
-        TimeBucket::History,
-        HISTORY_LIMIT,
-        options.show_all_history,
-        options,
-    );
+    let mut budget = options.limit;
+    let mut over_limit = 0;
+    for (bucket, entries) in buckets.groups() {
+        over_limit += push_rendered_bucket(
+            &mut rendered,
+            entries,
+            bucket,
+            options.limits.get(bucket),
+            &mut budget,
+            options,
+        );
+    }
 
-    let widths = column_widths(&rendered);
+    if options.grid {
+        let line_width = options.width.unwrap_or(DEFAULT_GRID_WIDTH);
+        for bucket in &rendered {
+            render_grid_bucket(bucket, line_width, options);
+        }
+        if let Some(footer) = limit_footer(over_limit) {
+            println!("{}", footer.dimmed());
+        }
+        print_footers(stats);
+        return Ok(());
+    }
+
+    let layout = plan_layout(column_widths(&rendered), options.long, options.width);
+    for bucket in &mut rendered {
+        for row in &mut bucket.rows {
+            row.name = truncate_name_for_human(
+                &row.name,
+                row.kind == EntryKind::Dir,
+                layout.widths.name,
+                options.truncate,
+            );
+        }
+    }
     for bucket in &rendered {
-        render_bucket(bucket, widths, options.use_icons);
+        render_bucket(bucket, &layout, options.icons);
     }
+    if let Some(footer) = limit_footer(over_limit) {
+        println!("{}", footer.dimmed());
+    }
+
+    print_footers(stats);
+    Ok(())
+}
 
+pub(crate) fn print_footers(stats: &ScanStats) {
     if let Some(footer) = unreadable_footer(stats) {
         println!("{footer}");
     }
-
-    Ok(())
+    if let Some(footer) = unresolved_links_footer(stats) {
+        println!("{footer}");
+    }
 }
 
+/// Render up to `preview_limit` rows of a bucket. `budget` is what is left of
+/// `--limit N`; buckets arrive newest first, so spending it in order keeps the
+/// newest N entries overall. A bucket reached after the budget is spent is
+/// left out, and its entry count is returned for the `--limit` footer.
 fn push_rendered_bucket(
     out: &mut Vec<RenderedBucket>,
     entries: &[FileEntry],
     bucket: TimeBucket,
-    preview_limit: usize,
-    show_all: bool,
+    preview_limit: Option<usize>,
+    budget: &mut Option<usize>,
     options: RenderOptions<'_>,
-) {
+) -> usize {
     if entries.is_empty() {
-        return;
+        return 0;
+    }
+    if *budget == Some(0) {
+        return entries.len();
     }
 
-    let shown = if show_all || entries.len() <= preview_limit {
-        entries.len()
-    } else {
-        preview_limit
-    };
+    let allowed = budget.map_or(entries.len(), |left| entries.len().min(left));
+    if let Some(left) = budget {
+        *left -= allowed;
+    }
+    let shown = preview_limit.map_or(allowed, |limit| allowed.min(limit));
 
     let rows = entries[..shown]
         .iter()
@@ -153,36 +255,297 @@ fn push_rendered_bucket(
 
     out.push(RenderedBucket {
         bucket,
-        header: bucket_header(bucket, shown, entries.len(), show_all),
+        header: bucket_header(bucket, shown, entries.len()),
         rows,
     });
+    0
 }
 
-fn render_bucket(bucket: &RenderedBucket, widths: ColumnWidths, use_icons: bool) {
-    println!("{}", style_header(bucket.bucket, &bucket.header, use_icons));
+fn render_bucket(bucket: &RenderedBucket, layout: &Layout, icons: Option<IconSet>) {
+    println!("{}", style_header(bucket.bucket, &bucket.header, icons));
 
+    let widths = layout.widths;
     for row in &bucket.rows {
         let type_label = pad_to_display_width(&row.type_label, widths.type_label, Alignment::Left);
-        let name = pad_to_display_width(&row.name, widths.name, Alignment::Left);
-        let size = pad_to_display_width(&row.size, widths.size, Alignment::Right);
         let time = pad_to_display_width(&row.time, widths.time, Alignment::Right);
         let suffix = if row.suffix.is_empty() {
             String::new()
         } else {
-            format!(" {}", row.suffix)
+            format!(" {}", theme::current().hint().paint(&row.suffix))
         };
 
+        let mut cells = vec![style_type_label(&type_label, row)];
+        if let Some(long) = &row.long {
+            cells.extend(long_cells(long, layout));
+        }
+        if widths.icon > 0 {
+            let icon = pad_to_display_width(row.icon, widths.icon, Alignment::Left);
+            cells.push(format!("{icon} {}", name_cell(row, widths.name)));
+        } else {
+            cells.push(name_cell(row, widths.name));
+        }
+        if layout.shows(Column::Size) {
+            cells.push(pad_to_display_width(
+                &row.size,
+                widths.size,
+                Alignment::Right,
+            ));
+        }
+        cells.push(style_time_text(row.bucket, &time));
+
+        println!("  {}{suffix}", cells.join("  "));
+    }
+
+    println!();
+}
+
+/// `--grid`: names only, laid out column-major like `ls -C` and colored by
+/// time tone.
+fn render_grid_bucket(bucket: &RenderedBucket, line_width: usize, options: RenderOptions<'_>) {
+    println!(
+        "{}",
+        style_header(bucket.bucket, &bucket.header, options.icons)
+    );
+
+    let icon_width = bucket
+        .rows
+        .iter()
+        .map(|row| display_width(row.icon))
+        .max()
+        .unwrap_or(0);
+    let icon_cell = if icon_width > 0 { icon_width + 1 } else { 0 };
+    let max_name_width = line_width.saturating_sub(ROW_INDENT + icon_cell).max(1);
+    let names: Vec<String> = bucket
+        .rows
+        .iter()
+        .map(|row| {
+            let name = truncate_name_for_human(
+                &row.name,
+                row.kind == EntryKind::Dir,
+                max_name_width,
+                options.truncate,
+            );
+            if icon_width > 0 {
+                let icon = pad_to_display_width(row.icon, icon_width, Alignment::Left);
+                format!("{icon} {name}")
+            } else {
+                name
+            }
+        })
+        .collect();
+    let widths: Vec<usize> = names.iter().map(|name| display_width(name)).collect();
+    let (row_count, column_widths) = grid_columns(&widths, line_width);
+
+    for line in 0..row_count {
+        let mut cells = Vec::new();
+        for (column, column_width) in column_widths.iter().enumerate() {
+            let index = column * row_count + line;
+            let Some(name) = names.get(index) else {
+                break;
+            };
+            let row = &bucket.rows[index];
+            let mut styled = style_time_text(row.bucket, name);
+            if let Some(url) = &row.link {
+                styled = hyperlink::wrap(&styled, url);
+            }
+            let is_last = index + row_count >= names.len();
+            if is_last {
+                cells.push(styled);
+            } else {
+                let padding = " ".repeat(column_width - widths[index]);
+                cells.push(format!("{styled}{padding}"));
+            }
+        }
+        println!("  {}", cells.join("  "));
+    }
+
+    println!();
+}
+
+/// Pick the fewest rows whose column-major layout fits `line_width`; returns
+/// the row count and each column's width.
+fn grid_columns(widths: &[usize], line_width: usize) -> (usize, Vec<usize>) {
+    for rows in 1..=widths.len().max(1) {
+        let column_widths: Vec<usize> = widths
+            .chunks(rows)
+            .map(|column| column.iter().copied().max().unwrap_or(0))
+            .collect();
+        let total = ROW_INDENT
+            + column_widths.iter().sum::<usize>()
+            + COLUMN_GAP * column_widths.len().saturating_sub(1);
+        if total <= line_width || column_widths.len() <= 1 {
+            return (rows, column_widths);
+        }
+    }
+
+    (
+        widths.len(),
+        vec![widths.iter().copied().max().unwrap_or(0)],
+    )
+}
+
+/// `--heatmap`: one column per week and one row per weekday, shaded by how
+/// many entries were modified that local day. Without color the shades fall
+/// back to `·░▒▓█`.
+pub fn render_heatmap(
+    entries: &[FileEntry],
+    now: SystemTime,
+    weeks: usize,
+    color_mode: ColorMode,
+) -> Result<()> {
+    let use_color = should_colorize(color_mode);
+    colored::control::set_override(use_color);
+
+    let today: DateTime<Local> = now.into();
+    let days = (weeks - 1) * 7 + today.weekday().num_days_from_monday() as usize + 1;
+    let timeline = Timeline::build(entries, now, TimelineBin::Day, days);
+    let max_count = timeline.bins.iter().map(|bin| bin.count).max().unwrap_or(0);
+
+    println!(
+        "{}",
+        format!(
+            "Activity (last {weeks} weeks, {} entries)",
+            timeline.total - timeline.earlier
+        )
+        .bold()
+    );
+    println!("    {}", heatmap_month_labels(&timeline, weeks));
+    for (weekday, label) in HEATMAP_WEEKDAYS.iter().enumerate() {
+        let cells: Vec<String> = timeline
+            .bins
+            .iter()
+            .skip(weekday)
+            .step_by(7)
+            .map(|bin| heatmap_cell(heat_level(bin.count, max_count), use_color))
+            .collect();
+        println!("{label} {}", cells.join(" "));
+    }
+
+    let legend: Vec<String> = (0..HEATMAP_GLYPHS.len())
+        .map(|level| heatmap_cell(level, use_color))
+        .collect();
+    println!(
+        "    {} {} {}",
+        "Less".dimmed(),
+        legend.join(" "),
+        "More".dimmed()
+    );
+
+    if timeline.earlier > 0 {
+        println!();
         println!(
-            "  {}  {}  {}  {}{}",
-            style_type_label(&type_label, row),
-            style_name(&name, row),
-            size,
-            style_time_text(row.bucket, &time),
-            suffix
+            "{}",
+            format!("{} earlier entries not shown", timeline.earlier).dimmed()
         );
     }
+    Ok(())
+}
 
-    println!();
+/// Abbreviated month names above the first week column of each month.
+fn heatmap_month_labels(timeline: &Timeline, weeks: usize) -> String {
+    let mut line = String::new();
+    let mut previous_month = None;
+    for column in 0..weeks {
+        let monday: DateTime<Local> = timeline.bins[column * 7].start.into();
+        let month = monday.month();
+        let position = column * 2;
+        if previous_month != Some(month) && line.len() <= position {
+            line.push_str(&" ".repeat(position - line.len()));
+            line.push_str(&monday.format("%b").to_string());
+        }
+        previous_month = Some(month);
+    }
+    line
+}
+
+/// Shade 0 for no activity, otherwise 1-4 relative to the busiest day.
+fn heat_level(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        return 0;
+    }
+    (count * 4).div_ceil(max).clamp(1, 4)
+}
+
+fn heatmap_cell(level: usize, use_color: bool) -> String {
+    if !use_color {
+        return HEATMAP_GLYPHS[level].to_string();
+    }
+
+    match level {
+        0 => "·".dimmed().to_string(),
+        1 => "■".green().dimmed().to_string(),
+        2 => "■".green().to_string(),
+        3 => "■".bright_green().to_string(),
+        _ => "■".bright_green().bold().to_string(),
+    }
+}
+
+/// Fit the name column to the terminal, dropping low-priority columns when
+/// even a minimal name column would not fit.
+fn plan_layout(widths: ColumnWidths, long: bool, terminal_width: Option<usize>) -> Layout {
+    let mut layout = Layout {
+        widths,
+        dropped: Vec::new(),
+    };
+    if !long {
+        layout
+            .dropped
+            .extend(DROP_ORDER.iter().filter(|c| **c != Column::Size));
+    }
+
+    let Some(terminal_width) = terminal_width else {
+        layout.widths.name = widths.name.min(DEFAULT_NAME_DISPLAY_WIDTH);
+        return layout;
+    };
+
+    let wanted = widths.name.min(MIN_NAME_DISPLAY_WIDTH);
+    let mut available = name_budget(&layout, terminal_width);
+    for column in DROP_ORDER {
+        if available >= wanted {
+            break;
+        }
+        if layout.shows(column) {
+            layout.dropped.push(column);
+            available = name_budget(&layout, terminal_width);
+        }
+    }
+
+    layout.widths.name = widths.name.min(available.max(wanted));
+    layout
+}
+
+/// Cells left for the name after the indent, the other visible columns, and gaps.
+fn name_budget(layout: &Layout, terminal_width: usize) -> usize {
+    let widths = layout.widths;
+    let others = [
+        (Column::Inode, widths.inode),
+        (Column::Group, widths.group),
+        (Column::Links, widths.links),
+        (Column::Owner, widths.owner),
+        (Column::Mode, widths.mode),
+        (Column::Size, widths.size),
+    ]
+    .into_iter()
+    .filter(|(column, _)| layout.shows(*column))
+    .map(|(_, width)| width + COLUMN_GAP);
+
+    let icon = if widths.icon > 0 { widths.icon + 1 } else { 0 };
+    let used = ROW_INDENT + widths.type_label + COLUMN_GAP + icon + widths.time + COLUMN_GAP;
+    terminal_width.saturating_sub(used + others.sum::<usize>())
+}
+
+/// Terminal width from `COLUMNS`, then from the stdout terminal itself.
+pub fn terminal_width() -> Option<usize> {
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|columns| *columns > 0)
+    {
+        return Some(columns);
+    }
+
+    terminal_size::terminal_size_of(std::io::stdout())
+        .map(|(terminal_size::Width(width), _)| usize::from(width))
 }
 
 fn render_row(entry: &FileEntry, bucket: TimeBucket, options: RenderOptions<'_>) -> RenderedRow {
@@ -195,23 +558,119 @@ fn render_row(entry: &FileEntry, bucket: TimeBucket, options: RenderOptions<'_>)
     RenderedRow {
         bucket,
         type_label: format_type_label(entry),
-        name: format_name(entry, options.base),
+        icon: icon::provider(options.icons).entry_icon(entry),
+        name: format_name(entry, options.base, options.quoting),
+        file_name: entry.name.clone(),
         size: format_size(entry),
         time,
         suffix: format_suffix(entry, options.now, bucket, options.scan_opts),
         kind: entry.kind,
+        link: options
+            .hyperlinks
+            .and_then(|linker| linker.url(&entry.path)),
+        long: options.long.then(|| long_columns(entry.long.as_ref())),
+    }
+}
+
+/// The padded, styled name. A link covers the name only, so the padding
+/// outside it keeps the column aligned and is not clickable.
+fn name_cell(row: &RenderedRow, width: usize) -> String {
+    let Some(url) = &row.link else {
+        let name = pad_to_display_width(&row.name, width, Alignment::Left);
+        return style_name(&name, row);
+    };
+
+    let padding = " ".repeat(width.saturating_sub(display_width(&row.name)));
+    let linked = hyperlink::wrap(&style_name(&row.name, row), url);
+    format!("{linked}{padding}")
+}
+
+fn long_columns(long: Option<&LongMetadata>) -> LongColumns {
+    let Some(long) = long else {
+        return LongColumns {
+            mode: "?".repeat(10),
+            links: "?".to_string(),
+            owner: "?".to_string(),
+            group: "?".to_string(),
+            inode: "?".to_string(),
+        };
+    };
+
+    LongColumns {
+        mode: format_mode(long.mode),
+        links: long.nlink.to_string(),
+        owner: long.owner.clone(),
+        group: long.group.clone(),
+        inode: long.inode.to_string(),
     }
 }
 
+fn long_cells(long: &LongColumns, layout: &Layout) -> Vec<String> {
+    let widths = layout.widths;
+    [
+        (Column::Mode, &long.mode, widths.mode, Alignment::Left),
+        (Column::Links, &long.links, widths.links, Alignment::Right),
+        (Column::Owner, &long.owner, widths.owner, Alignment::Left),
+        (Column::Group, &long.group, widths.group, Alignment::Left),
+        (Column::Inode, &long.inode, widths.inode, Alignment::Right),
+    ]
+    .into_iter()
+    .filter(|(column, ..)| layout.shows(*column))
+    .map(|(_, value, width, alignment)| pad_to_display_width(value, width, alignment))
+    .collect()
+}
+
+/// Render `st_mode` the way `ls -l` does, including setuid/setgid/sticky bits.
+pub(crate) fn format_mode(mode: u32) -> String {
+    let file_type = match mode & 0o170_000 {
+        0o040_000 => 'd',
+        0o120_000 => 'l',
+        0o020_000 => 'c',
+        0o060_000 => 'b',
+        0o010_000 => 'p',
+        0o140_000 => 's',
+        _ => '-',
+    };
+
+    let triplet = |read: u32, write: u32, exec: u32, special: u32, set: char| {
+        let exec_char = match (mode & exec != 0, mode & special != 0) {
+            (true, true) => set,
+            (false, true) => set.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        };
+        [
+            if mode & read != 0 { 'r' } else { '-' },
+            if mode & write != 0 { 'w' } else { '-' },
+            exec_char,
+        ]
+    };
+
+    let mut out = String::with_capacity(10);
+    out.push(file_type);
+    out.extend(triplet(0o400, 0o200, 0o100, 0o4000, 's'));
+    out.extend(triplet(0o040, 0o020, 0o010, 0o2000, 's'));
+    out.extend(triplet(0o004, 0o002, 0o001, 0o1000, 't'));
+    out
+}
+
 fn column_widths(buckets: &[RenderedBucket]) -> ColumnWidths {
     let mut widths = ColumnWidths::default();
 
     for bucket in buckets {
         for row in &bucket.rows {
             widths.type_label = widths.type_label.max(display_width(&row.type_label));
+            widths.icon = widths.icon.max(display_width(row.icon));
             widths.name = widths.name.max(display_width(&row.name));
             widths.size = widths.size.max(display_width(&row.size));
             widths.time = widths.time.max(display_width(&row.time));
+            if let Some(long) = &row.long {
+                widths.mode = widths.mode.max(display_width(&long.mode));
+                widths.links = widths.links.max(display_width(&long.links));
+                widths.owner = widths.owner.max(display_width(&long.owner));
+                widths.group = widths.group.max(display_width(&long.group));
+                widths.inode = widths.inode.max(display_width(&long.inode));
+            }
         }
     }
 
@@ -222,51 +681,46 @@ fn display_width(text: &str) -> usize {
     UnicodeWidthStr::width(text)
 }
 
-fn bucket_header(bucket: TimeBucket, shown: usize, total: usize, show_all: bool) -> String {
-    if show_all || shown == total {
+fn bucket_header(bucket: TimeBucket, shown: usize, total: usize) -> String {
+    if shown == total {
         format!("{} ({total})", bucket.title())
     } else {
         format!("{} ({shown}/{total})", bucket.title())
     }
 }
 
-fn style_header(bucket: TimeBucket, header: &str, use_icons: bool) -> String {
-    let icon = icon_prefix(bucket, use_icons);
+fn style_header(bucket: TimeBucket, header: &str, icons: Option<IconSet>) -> String {
+    let icon = icon::provider(icons).bucket_icon(bucket);
     let text = if icon.is_empty() {
         header.to_string()
     } else {
         format!("{icon} {header}")
     };
 
-    match bucket {
-        TimeBucket::Active => text.green().bold().to_string(),
-        TimeBucket::Today => text.bold().to_string(),
-        TimeBucket::ThisWeek => text.cyan().bold().to_string(),
-        TimeBucket::History => text.to_string(),
-    }
+    theme::current().header(bucket).paint(&text)
 }
 
-fn format_name(entry: &FileEntry, base: &Path) -> String {
-    let rel = entry
-        .path
-        .strip_prefix(base)
-        .map(|p| p.display().to_string())
-        .unwrap_or_else(|_| entry.name.clone());
+pub(crate) fn format_name(entry: &FileEntry, base: &Path, quoting: QuotingStyle) -> String {
+    let rel = quote(entry.relative_path(base), quoting);
 
-    let name = if entry.is_dir() {
+    if entry.is_dir() {
         format!("{rel}/")
     } else {
         rel
-    };
-
-    truncate_name_for_human(&name, entry.is_dir())
+    }
 }
 
-fn format_type_label(entry: &FileEntry) -> String {
+pub(crate) fn format_type_label(entry: &FileEntry) -> String {
     match entry.kind {
         EntryKind::File => "[FIL]".to_string(),
         EntryKind::Dir => "[DIR]".to_string(),
         EntryKind::Symlink => "[LNK]".to_string(),
+        EntryKind::SymlinkDir => "[LND]".to_string(),
+        EntryKind::BrokenSymlink => "[BRK]".to_string(),
+        EntryKind::Fifo => "[FIF]".to_string(),
+        EntryKind::Socket => "[SOC]".to_string(),
+        EntryKind::BlockDevice => "[BLK]".to_string(),
+        EntryKind::CharDevice => "[CHR]".to_string(),
     }
 }
 
@@ -292,30 +746,21 @@ fn classify_time_tone(bucket: TimeBucket, time_str: &str) -> TimeTone {
     }
 }
 
-fn style_time_text(bucket: TimeBucket, time_str: &str) -> String {
-    match classify_time_tone(bucket, time_str) {
-        TimeTone::Skew => time_str.yellow().bold().to_string(),
-        TimeTone::Active => time_str.green().bold().to_string(),
-        TimeTone::Today => time_str.normal().to_string(),
-        TimeTone::ThisWeek => time_str.cyan().to_string(),
-        TimeTone::History => time_str.to_string(),
-    }
+pub(crate) fn style_time_text(bucket: TimeBucket, time_str: &str) -> String {
+    let theme = theme::current();
+    let style = match classify_time_tone(bucket, time_str) {
+        TimeTone::Skew => theme.skew(),
+        _ => theme.time(bucket),
+    };
+    style.paint(time_str)
 }
 
 fn style_name(text: &str, row: &RenderedRow) -> String {
-    match row.kind {
-        EntryKind::Dir => text.blue().bold().to_string(),
-        EntryKind::Symlink => text.magenta().underline().to_string(),
-        EntryKind::File => text.to_string(),
-    }
+    theme::current().name(row.kind, &row.file_name).paint(text)
 }
 
 fn style_type_label(text: &str, row: &RenderedRow) -> String {
-    match row.kind {
-        EntryKind::Dir => text.blue().bold().to_string(),
-        EntryKind::Symlink => text.magenta().to_string(),
-        EntryKind::File => text.dimmed().to_string(),
-    }
+    theme::current().label(row.kind).paint(text)
 }
 
 fn format_child_activity_hint_suffix(
@@ -340,19 +785,29 @@ fn format_child_activity_hint(hint: ChildActivityHint) -> String {
     }
 }
 
-fn format_size(entry: &FileEntry) -> String {
+pub(crate) fn format_size(entry: &FileEntry) -> String {
     if entry.is_symlink() {
         return "<lnk>".to_string();
     }
 
-    if entry.is_dir() {
-        return "<dir>".to_string();
+    match entry.kind {
+        EntryKind::Dir => return "<dir>".to_string(),
+        EntryKind::Fifo => return "<fifo>".to_string(),
+        EntryKind::Socket => return "<sock>".to_string(),
+        EntryKind::BlockDevice => return "<blk>".to_string(),
+        EntryKind::CharDevice => return "<chr>".to_string(),
+        _ => {}
     }
 
     let Some(size) = entry.size else {
         return "—".to_string();
     };
 
+    format_byte_size(size)
+}
+
+/// Compact binary size such as `312 B`, `8.4 KiB`, or `12 MiB`.
+pub fn format_byte_size(size: u64) -> String {
     if size < 1024 {
         return format!("{size} B");
     }
@@ -377,12 +832,12 @@ fn format_size(entry: &FileEntry) -> String {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Alignment {
+pub(crate) enum Alignment {
     Left,
     Right,
 }
 
-fn pad_to_display_width(text: &str, width: usize, alignment: Alignment) -> String {
+pub(crate) fn pad_to_display_width(text: &str, width: usize, alignment: Alignment) -> String {
     let padding = " ".repeat(width.saturating_sub(display_width(text)));
     match alignment {
         Alignment::Left => format!("{text}{padding}"),
@@ -390,32 +845,51 @@ fn pad_to_display_width(text: &str, width: usize, alignment: Alignment) -> Strin
     }
 }
 
-fn truncate_name_for_human(name: &str, is_dir: bool) -> String {
-    if display_width(name) <= MAX_NAME_DISPLAY_WIDTH {
+fn truncate_name_for_human(
+    name: &str,
+    is_dir: bool,
+    max_width: usize,
+    mode: TruncateMode,
+) -> String {
+    if display_width(name) <= max_width {
         return name.to_string();
     }
 
-    if is_dir {
-        return truncate_directory_name(name);
+    match (mode, is_dir) {
+        (TruncateMode::Suffix, true) => truncate_directory_name(name, max_width),
+        (TruncateMode::Suffix, false) => truncate_file_name(name, max_width),
+        (TruncateMode::Middle, true) => {
+            let base = name.strip_suffix('/').unwrap_or(name);
+            format!("{}/", truncate_middle(base, max_width.saturating_sub(1)))
+        }
+        (TruncateMode::Middle, false) => truncate_middle(name, max_width),
     }
-
-    truncate_file_name(name)
 }
 
-fn truncate_directory_name(name: &str) -> String {
+fn truncate_directory_name(name: &str, max_width: usize) -> String {
     let base = name.strip_suffix('/').unwrap_or(name);
-    truncate_with_suffix(base, "~/")
+    truncate_with_suffix(base, "~/", max_width)
 }
 
-fn truncate_file_name(name: &str) -> String {
+fn truncate_file_name(name: &str, max_width: usize) -> String {
     if let Some((stem, ext)) = split_extension(name) {
         let suffix = format!("~{ext}");
-        if display_width(&suffix) < MAX_NAME_DISPLAY_WIDTH {
-            return truncate_with_suffix(stem, &suffix);
+        if display_width(&suffix) < max_width {
+            return truncate_with_suffix(stem, &suffix, max_width);
         }
     }
 
-    truncate_with_suffix(name, "~")
+    truncate_with_suffix(name, "~", max_width)
+}
+
+/// Keep the start and the end of `text`, joined by `~`; the start gets the
+/// extra cell when the budget is odd.
+fn truncate_middle(text: &str, max_width: usize) -> String {
+    let available = max_width.saturating_sub(1);
+    let tail_width = available / 2;
+    let head = take_prefix_by_display_width(text, available - tail_width);
+    let tail = take_suffix_by_display_width(text, tail_width);
+    format!("{head}~{tail}")
 }
 
 fn split_extension(name: &str) -> Option<(&str, &str)> {
@@ -427,8 +901,8 @@ fn split_extension(name: &str) -> Option<(&str, &str)> {
     Some((&name[..dot], &name[dot..]))
 }
 
-fn truncate_with_suffix(text: &str, suffix: &str) -> String {
-    let available = MAX_NAME_DISPLAY_WIDTH.saturating_sub(display_width(suffix));
+fn truncate_with_suffix(text: &str, suffix: &str, max_width: usize) -> String {
+    let available = max_width.saturating_sub(display_width(suffix));
     let prefix = take_prefix_by_display_width(text, available);
     format!("{prefix}{suffix}")
 }
@@ -449,6 +923,22 @@ fn take_prefix_by_display_width(text: &str, max_width: usize) -> String {
     out
 }
 
+fn take_suffix_by_display_width(text: &str, max_width: usize) -> String {
+    let mut chars = Vec::new();
+    let mut used = 0;
+
+    for ch in text.chars().rev() {
+        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + ch_width > max_width {
+            break;
+        }
+        chars.push(ch);
+        used += ch_width;
+    }
+
+    chars.into_iter().rev().collect()
+}
+
 fn filters_summary(scan_opts: &ScanOptions) -> Option<String> {
     let dots = match scan_opts.dot_mode {
         crate::engine::DotMode::Default => "default".to_string(),
@@ -479,12 +969,27 @@ fn filters_summary(scan_opts: &ScanOptions) -> Option<String> {
     }
 }
 
-fn unreadable_footer(stats: &ScanStats) -> Option<String> {
+pub(crate) fn unreadable_footer(stats: &ScanStats) -> Option<String> {
     (stats.skipped_unreadable > 0)
         .then(|| format!("Skipped {} unreadable entries", stats.skipped_unreadable))
 }
 
-fn should_colorize(mode: ColorMode) -> bool {
+/// Entries of whole buckets that `--limit N` left out.
+fn limit_footer(over_limit: usize) -> Option<String> {
+    (over_limit > 0)
+        .then(|| format!("… {over_limit} more in older buckets (raise -n to show them)"))
+}
+
+pub(crate) fn unresolved_links_footer(stats: &ScanStats) -> Option<String> {
+    (stats.unresolved_links > 0).then(|| {
+        format!(
+            "Could not follow {} symlinks (broken or looping)",
+            stats.unresolved_links
+        )
+    })
+}
+
+pub(crate) fn should_colorize(mode: ColorMode) -> bool {
     match mode {
         ColorMode::Always => true,
         ColorMode::Never => false,
@@ -494,20 +999,6 @@ fn should_colorize(mode: ColorMode) -> bool {
     }
 }
 
-fn icon_prefix(bucket: TimeBucket, use_icons: bool) -> &'static str {
-    #[cfg(feature = "icons")]
-    {
-        if use_icons {
-            let provider = NerdIconProvider;
-            return provider.bucket_icon(bucket);
-        }
-    }
-
-    let _ = use_icons;
-    let provider = DefaultIconProvider;
-    provider.bucket_icon(bucket)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,11 +1030,18 @@ mod tests {
 
     #[test]
     fn bucket_headers_show_preview_counts() {
+        assert_eq!(bucket_header(TimeBucket::History, 5, 7), "History (5/7)");
+        assert_eq!(bucket_header(TimeBucket::Active, 3, 3), "Active (3)");
+        assert_eq!(bucket_header(TimeBucket::Today, 0, 4), "Today (0/4)");
+    }
+
+    #[test]
+    fn limit_footer_counts_buckets_left_out_by_the_limit() {
+        assert_eq!(limit_footer(0), None);
         assert_eq!(
-            bucket_header(TimeBucket::History, 5, 7, false),
-            "History (5/7)"
+            limit_footer(3),
+            Some("… 3 more in older buckets (raise -n to show them)".to_string())
         );
-        assert_eq!(bucket_header(TimeBucket::Active, 3, 3, false), "Active (3)");
     }
 
     #[test]
@@ -564,6 +1062,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unresolved_links_footer_is_only_rendered_when_links_were_not_followed() {
+        assert_eq!(unresolved_links_footer(&ScanStats::default()), None);
+
+        let stats = ScanStats {
+            unresolved_links: 3,
+            ..ScanStats::default()
+        };
+        assert_eq!(
+            unresolved_links_footer(&stats),
+            Some("Could not follow 3 symlinks (broken or looping)".to_string())
+        );
+    }
+
     #[test]
     fn column_widths_use_the_longest_visible_row_across_buckets() {
         let buckets = vec![
@@ -573,11 +1085,15 @@ mod tests {
                 rows: vec![RenderedRow {
                     bucket: TimeBucket::Today,
                     type_label: "[FIL]".to_string(),
-                    name: "README.md".to_string(),
+                    icon: "📝",
+                    name: "README.md".into(),
+                    file_name: "README.md".into(),
                     size: "1.2 KiB".to_string(),
                     time: "2h".to_string(),
                     suffix: String::new(),
                     kind: EntryKind::File,
+                    link: None,
+                    long: None,
                 }],
             },
             RenderedBucket {
@@ -586,11 +1102,15 @@ mod tests {
                 rows: vec![RenderedRow {
                     bucket: TimeBucket::History,
                     type_label: "[LNK]".to_string(),
-                    name: "link_to_readme".to_string(),
+                    icon: "@",
+                    name: "link_to_readme".into(),
+                    file_name: "link_to_readme".into(),
                     size: "—".to_string(),
                     time: "2026-03-01".to_string(),
                     suffix: "-> README.md".to_string(),
                     kind: EntryKind::Symlink,
+                    link: None,
+                    long: None,
                 }],
             },
         ];
@@ -599,13 +1119,25 @@ mod tests {
             column_widths(&buckets),
             ColumnWidths {
                 type_label: display_width("[FIL]"),
+                icon: 2,
                 name: display_width("link_to_readme"),
                 size: display_width("1.2 KiB"),
                 time: display_width("2026-03-01"),
+                ..ColumnWidths::default()
             }
         );
     }
 
+    #[test]
+    fn format_mode_matches_ls_long_listing() {
+        assert_eq!(format_mode(0o100_644), "-rw-r--r--");
+        assert_eq!(format_mode(0o040_755), "drwxr-xr-x");
+        assert_eq!(format_mode(0o120_777), "lrwxrwxrwx");
+        assert_eq!(format_mode(0o104_755), "-rwsr-xr-x");
+        assert_eq!(format_mode(0o041_777), "drwxrwxrwt");
+        assert_eq!(format_mode(0o102_644), "-rw-r-Sr--");
+    }
+
     #[test]
     fn display_width_counts_full_width_cells() {
         assert_eq!(display_width("日本語.txt"), 10);
@@ -614,7 +1146,12 @@ mod tests {
     #[test]
     fn truncate_name_for_human_preserves_file_extension() {
         assert_eq!(
-            truncate_name_for_human("あいうえおかきくけこさしすせそ.pdf", false),
+            truncate_name_for_human(
+                "あいうえおかきくけこさしすせそ.pdf",
+                false,
+                DEFAULT_NAME_DISPLAY_WIDTH,
+                TruncateMode::Suffix
+            ),
             "あいうえおかきくけこさ~.pdf"
         );
     }
@@ -622,21 +1159,102 @@ mod tests {
     #[test]
     fn truncate_name_for_human_keeps_directory_slash() {
         assert_eq!(
-            truncate_name_for_human("あいうえおかきくけこさしすせそ/", true),
+            truncate_name_for_human(
+                "あいうえおかきくけこさしすせそ/",
+                true,
+                DEFAULT_NAME_DISPLAY_WIDTH,
+                TruncateMode::Suffix
+            ),
             "あいうえおかきくけこさしす~/"
         );
     }
 
+    #[test]
+    fn truncate_middle_keeps_both_ends() {
+        assert_eq!(
+            truncate_name_for_human(
+                "quarterly-report-draft-3.pdf",
+                false,
+                15,
+                TruncateMode::Middle
+            ),
+            "quarter~t-3.pdf"
+        );
+        assert_eq!(
+            truncate_name_for_human("build-artifacts-cache/", true, 12, TruncateMode::Middle),
+            "build~cache/"
+        );
+    }
+
+    #[test]
+    fn grid_columns_uses_the_fewest_rows_that_fit() {
+        assert_eq!(grid_columns(&[4, 4, 4, 4], 80), (1, vec![4, 4, 4, 4]));
+        assert_eq!(
+            grid_columns(&[10, 3, 10, 3], 2 + 10 + 2 + 10),
+            (2, vec![10, 10])
+        );
+        assert_eq!(grid_columns(&[30, 30, 30], 40), (3, vec![30]));
+        assert_eq!(grid_columns(&[], 80), (1, vec![]));
+    }
+
+    #[test]
+    fn heat_level_scales_counts_into_four_shades() {
+        assert_eq!(heat_level(0, 10), 0);
+        assert_eq!(heat_level(1, 10), 1);
+        assert_eq!(heat_level(5, 10), 2);
+        assert_eq!(heat_level(6, 10), 3);
+        assert_eq!(heat_level(10, 10), 4);
+        assert_eq!(heatmap_cell(3, false), "▓");
+    }
+
+    #[test]
+    fn plan_layout_fills_the_terminal_and_drops_columns_when_narrow() {
+        let widths = ColumnWidths {
+            type_label: 5,
+            icon: 0,
+            name: 60,
+            size: 7,
+            time: 3,
+            mode: 10,
+            links: 1,
+            owner: 8,
+            group: 8,
+            inode: 8,
+        };
+
+        assert_eq!(
+            plan_layout(widths, false, None).widths.name,
+            DEFAULT_NAME_DISPLAY_WIDTH
+        );
+
+        let wide = plan_layout(widths, false, Some(120));
+        assert_eq!(wide.widths.name, 60);
+        assert!(wide.shows(Column::Size));
+
+        let medium = plan_layout(widths, false, Some(50));
+        assert_eq!(medium.widths.name, 50 - (2 + 5 + 2 + 7 + 2 + 3 + 2));
+
+        let with_icons = ColumnWidths { icon: 2, ..widths };
+        let medium_icons = plan_layout(with_icons, false, Some(50));
+        assert_eq!(medium_icons.widths.name, medium.widths.name - 3);
+
+        let narrow_long = plan_layout(widths, true, Some(60));
+        assert!(!narrow_long.shows(Column::Inode));
+        assert!(!narrow_long.shows(Column::Group));
+        assert!(narrow_long.shows(Column::Mode));
+        assert!(narrow_long.widths.name >= MIN_NAME_DISPLAY_WIDTH);
+    }
+
     #[test]
     fn semantic_palette_is_theme_safe() {
         colored::control::set_override(true);
 
         assert_eq!(
-            style_header(TimeBucket::ThisWeek, "This Week (1)", false),
+            style_header(TimeBucket::ThisWeek, "This Week (1)", None),
             "\u{1b}[1;36mThis Week (1)\u{1b}[0m"
         );
         assert_eq!(
-            style_header(TimeBucket::History, "History (1)", false),
+            style_header(TimeBucket::History, "History (1)", None),
             "History (1)"
         );
         assert_eq!(
@@ -477,37 +477,134 @@ fn global_ignore_file_is_respected_and_no_ignore_overrides() {
 
 #[cfg(unix)]
 #[test]
-fn self_update_runs_installer_for_current_binary_dir() {
+fn self_update_installs_the_verified_release_into_the_current_binary_dir() {
     let dir = tempdir().unwrap();
-    let script_path = dir.path().join("install.sh");
-    let marker_path = dir.path().join("marker.txt");
+    let release = support::fake_release(dir.path(), "9.9.9");
+    let install_dir = dir.path().join("bin");
+    fs::create_dir_all(&install_dir).unwrap();
+    fs::write(install_dir.join("ftime"), "old").unwrap();
 
+    let output = bin()
+        .arg("--self-update")
+        .env(
+            "FTIME_SELF_UPDATE_URL",
+            format!("file://{}", release.display()),
+        )
+        .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
+        .env("FTIME_SELF_UPDATE_INSTALL_DIR", &install_dir)
+        .env_remove("FTIME_SELF_UPDATE_PUBLIC_KEY")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!(
+        "ftime updated {} -> 9.9.9 in {}",
+        support::package_version(),
+        install_dir.display()
+    )));
+    let installed = fs::read_to_string(install_dir.join("ftime")).unwrap();
+    assert!(installed.contains("ftime 9.9.9"));
+    assert!(!install_dir.join(".ftime.new").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn self_update_refuses_a_checksum_mismatch_and_keeps_the_binary() {
+    let dir = tempdir().unwrap();
+    let release = support::fake_release(dir.path(), "9.9.9");
+    let archive = support::release_archive_name("9.9.9");
     fs::write(
-        &script_path,
-        "#!/usr/bin/env bash\nset -euo pipefail\nprintf '%s\\n' \"$INSTALL_DIR\" > \"$FTIME_SELF_UPDATE_MARKER\"\n",
+        release.join("SHA256SUMS"),
+        format!("{}  {archive}\n", "0".repeat(64)),
     )
     .unwrap();
+    let install_dir = dir.path().join("bin");
+    fs::create_dir_all(&install_dir).unwrap();
+    fs::write(install_dir.join("ftime"), "old").unwrap();
 
-    let output = bin()
+    bin()
         .arg("--self-update")
         .env(
             "FTIME_SELF_UPDATE_URL",
-            format!("file://{}", script_path.display()),
+            format!("file://{}", release.display()),
         )
+        .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
+        .env("FTIME_SELF_UPDATE_INSTALL_DIR", &install_dir)
+        .env_remove("FTIME_SELF_UPDATE_PUBLIC_KEY")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "refusing to update: SHA-256 mismatch for {archive}"
+        )));
+    assert_eq!(
+        fs::read_to_string(install_dir.join("ftime")).unwrap(),
+        "old"
+    );
+
+    fs::remove_file(release.join("SHA256SUMS")).unwrap();
+    bin()
+        .arg("--self-update")
         .env(
-            "FTIME_SELF_UPDATE_INSTALL_DIR",
-            dir.path().display().to_string(),
+            "FTIME_SELF_UPDATE_URL",
+            format!("file://{}", release.display()),
         )
-        .env("FTIME_SELF_UPDATE_MARKER", &marker_path)
-        .output()
-        .unwrap();
-
-    assert!(output.status.success());
-    let stdout = String::from_utf8(output.stdout).unwrap();
-    assert!(stdout.contains("self-update completed"));
+        .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
+        .env("FTIME_SELF_UPDATE_INSTALL_DIR", &install_dir)
+        .env_remove("FTIME_SELF_UPDATE_PUBLIC_KEY")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to update without a checksums file",
+        ));
+    assert_eq!(
+        fs::read_to_string(install_dir.join("ftime")).unwrap(),
+        "old"
+    );
+}
 
-    let recorded = fs::read_to_string(marker_path).unwrap();
-    assert_eq!(recorded.trim(), dir.path().display().to_string());
+#[cfg(unix)]
+#[test]
+fn self_update_checks_the_checksums_signature_when_a_key_is_configured() {
+    let dir = tempdir().unwrap();
+    let release = support::fake_release(dir.path(), "9.9.9");
+    let install_dir = dir.path().join("bin");
+    fs::create_dir_all(&install_dir).unwrap();
+    let (public_key, signature) = support::minisign(&fs::read(release.join("SHA256SUMS")).unwrap());
+    let update = || {
+        let mut cmd = bin();
+        cmd.arg("--self-update")
+            .env(
+                "FTIME_SELF_UPDATE_URL",
+                format!("file://{}", release.display()),
+            )
+            .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
+            .env("FTIME_SELF_UPDATE_INSTALL_DIR", &install_dir)
+            .env("FTIME_SELF_UPDATE_PUBLIC_KEY", &public_key);
+        cmd
+    };
+
+    update().assert().failure().stderr(predicate::str::contains(
+        "refusing to update without a signature for SHA256SUMS",
+    ));
+    assert!(!install_dir.join("ftime").exists());
+
+    let mut tampered = fs::read(release.join("SHA256SUMS")).unwrap();
+    tampered.extend(b"0000  extra.tar.gz\n");
+    let (_, tampered_signature) = support::minisign(&tampered);
+    fs::write(release.join("SHA256SUMS.minisig"), tampered_signature).unwrap();
+    update().assert().failure().stderr(predicate::str::contains(
+        "refusing to update: bad signature on SHA256SUMS",
+    ));
+    assert!(!install_dir.join("ftime").exists());
+
+    fs::write(release.join("SHA256SUMS.minisig"), signature).unwrap();
+    update().assert().success();
+    assert!(
+        fs::read_to_string(install_dir.join("ftime"))
+            .unwrap()
+            .contains("ftime 9.9.9")
+    );
 }
 
 #[cfg(unix)]
@@ -526,27 +623,31 @@ fn self_update_prefers_invoked_symlink_directory() {
     let link_bin = link_dir.join("ftime");
     std::os::unix::fs::symlink(&real_bin, &link_bin).unwrap();
 
-    let script_path = dir.path().join("install.sh");
-    let marker_path = dir.path().join("marker.txt");
-    fs::write(
-        &script_path,
-        "#!/usr/bin/env bash\nset -euo pipefail\nprintf '%s\\n' \"$INSTALL_DIR\" > \"$FTIME_SELF_UPDATE_MARKER\"\n",
-    )
-    .unwrap();
+    let release = support::fake_release(dir.path(), "9.9.9");
 
     let output = Command::new(&link_bin)
         .arg("--self-update")
         .env(
             "FTIME_SELF_UPDATE_URL",
-            format!("file://{}", script_path.display()),
+            format!("file://{}", release.display()),
         )
-        .env("FTIME_SELF_UPDATE_MARKER", &marker_path)
+        .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
+        .env_remove("FTIME_SELF_UPDATE_PUBLIC_KEY")
         .output()
         .unwrap();
 
-    assert!(output.status.success());
-    let recorded = fs::read_to_string(marker_path).unwrap();
-    assert_eq!(recorded.trim(), link_dir.display().to_string());
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains(&format!("in {}", link_dir.display())),
+        "{stdout}"
+    );
+    assert!(
+        fs::read_to_string(link_dir.join("ftime"))
+            .unwrap()
+            .contains("ftime 9.9.9")
+    );
+    assert_eq!(fs::read(&real_bin).unwrap(), fs::read(source_bin).unwrap());
 }
 
 #[test]
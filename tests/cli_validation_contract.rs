@@ -91,6 +91,18 @@ fn plain_rejects_human_only_flags_but_keeps_absolute() {
         .success();
 }
 
+#[test]
+fn plain_rejects_long_listing() {
+    bin()
+        .arg("--plain")
+        .arg("--long")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--plain cannot be combined with --long",
+        ));
+}
+
 #[test]
 fn update_flags_reject_each_other_and_scan_inputs() {
     let dir = tempdir().unwrap();
@@ -131,22 +143,576 @@ fn self_update_warns_for_probable_cargo_bin_installs() {
     let cargo_bin = home.join(".cargo").join("bin");
     fs::create_dir_all(&cargo_bin).unwrap();
 
-    let script_path = tmp.path().join("install.sh");
-    fs::write(&script_path, "#!/usr/bin/env bash\nset -euo pipefail\n").unwrap();
+    let release = support::fake_release(tmp.path(), "9.9.9");
 
     bin()
         .arg("--self-update")
         .env("HOME", &home)
         .env(
             "FTIME_SELF_UPDATE_URL",
-            format!("file://{}", script_path.display()),
+            format!("file://{}", release.display()),
         )
+        .env("FTIME_SELF_UPDATE_LATEST_VERSION", "9.9.9")
         .env("FTIME_SELF_UPDATE_INSTALL_DIR", &cargo_bin)
+        .env_remove("FTIME_SELF_UPDATE_PUBLIC_KEY")
         .assert()
         .success()
-        .stdout(predicate::str::contains("self-update completed"))
+        .stdout(predicate::str::contains("ftime updated"))
         .stderr(predicate::str::contains(
             "warning: --self-update is intended for GitHub Releases installs;",
         ))
         .stderr(predicate::str::contains("cargo install"));
 }
+
+#[test]
+fn hash_requires_machine_output_and_max_size_requires_hash() {
+    bin()
+        .arg("--hash")
+        .arg("sha256")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--hash requires --plain, --json, --json-doc, --format, --csv, or --tsv",
+        ));
+
+    bin()
+        .arg("--plain")
+        .arg("--hash-max-size")
+        .arg("1M")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--hash-max-size requires --hash"));
+
+    bin()
+        .arg("--plain")
+        .arg("--hash")
+        .arg("sha256")
+        .arg("--hash-max-size")
+        .arg("lots")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value for --hash-max-size",
+        ));
+}
+
+#[test]
+fn format_rejects_unknown_placeholders_and_other_output_modes() {
+    bin()
+        .arg("--format")
+        .arg("{path}\\t{colour}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid --format template: unknown placeholder `{colour}`",
+        ));
+
+    bin()
+        .arg("--format")
+        .arg("{path}")
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--plain and --format cannot be combined",
+        ));
+
+    bin()
+        .arg("--format")
+        .arg("{path}")
+        .arg("--hints")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format cannot be combined with human-only flags",
+        ));
+
+    bin()
+        .arg("--format")
+        .arg("{path} {hash}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format placeholder {hash} requires --hash",
+        ));
+}
+
+#[test]
+fn csv_tsv_and_print0_reject_other_output_modes_and_stray_column_flags() {
+    bin()
+        .arg("--csv")
+        .arg("--tsv")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--csv and --tsv cannot be combined",
+        ));
+
+    bin()
+        .arg("--json")
+        .arg("--print0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--json and --print0 cannot be combined",
+        ));
+
+    bin()
+        .arg("--csv")
+        .arg("--absolute")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--csv cannot be combined with human-only flags",
+        ));
+
+    bin()
+        .arg("--plain")
+        .arg("--no-header")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--columns and --no-header require --csv or --tsv",
+        ));
+
+    bin()
+        .arg("--tsv")
+        .arg("--columns")
+        .arg("path,colour")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value for --columns: unknown column `colour`",
+        ));
+
+    bin()
+        .arg("--csv")
+        .arg("--columns")
+        .arg("path,hash")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--columns hash requires --hash"));
+}
+
+#[test]
+fn json_doc_rejects_other_output_modes_and_human_only_flags() {
+    bin()
+        .arg("--json")
+        .arg("--json-doc")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--json and --json-doc cannot be combined",
+        ));
+
+    bin()
+        .arg("--json-doc")
+        .arg("--hints")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--json-doc cannot be combined with human-only flags",
+        ));
+}
+
+#[test]
+fn json_version_requires_json_and_a_known_version() {
+    bin()
+        .arg("--plain")
+        .arg("--json-version")
+        .arg("2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--json-version requires --json"));
+
+    bin()
+        .arg("--json")
+        .arg("--json-version")
+        .arg("3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("3 is not in 1..=2"));
+
+    bin()
+        .arg("schema")
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '--plain'"));
+}
+
+#[test]
+fn raw_bytes_requires_plain() {
+    bin()
+        .arg("--json")
+        .arg("--raw-bytes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--raw-bytes requires --plain"));
+}
+
+#[test]
+fn quoting_style_only_applies_to_human_and_plain_output() {
+    bin()
+        .arg("--json")
+        .arg("--quoting-style")
+        .arg("shell")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--quoting-style only applies to the human view and --plain",
+        ));
+
+    bin()
+        .arg("--plain")
+        .arg("--raw-bytes")
+        .arg("--quoting-style")
+        .arg("escape")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--raw-bytes cannot be combined with --quoting-style other than literal",
+        ));
+}
+
+#[test]
+fn truncate_only_applies_to_the_human_view() {
+    bin()
+        .arg("--plain")
+        .arg("--truncate")
+        .arg("middle")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--truncate only applies to the human view",
+        ));
+}
+
+#[test]
+fn grid_rejects_row_only_flags_and_machine_output() {
+    bin()
+        .arg("--grid")
+        .arg("--long")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--grid cannot be combined with --long or --hints",
+        ));
+
+    bin()
+        .arg("--grid")
+        .arg("--json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--grid only applies to the human view",
+        ));
+}
+
+#[test]
+fn tree_rejects_row_flags_machine_output_and_stray_collapse() {
+    bin()
+        .arg("--tree")
+        .arg("--grid")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--tree cannot be combined with --grid, --long, --hints, --all-history, or --absolute",
+        ));
+
+    bin()
+        .arg("--tree")
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--tree only applies to the human view",
+        ));
+
+    bin()
+        .arg("--tree-collapse")
+        .arg("1d")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--tree-collapse requires --tree"));
+
+    bin()
+        .arg("--tree")
+        .arg("--tree-collapse")
+        .arg("soon")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value for --tree-collapse",
+        ));
+}
+
+#[test]
+fn timeline_accepts_only_human_or_json_output() {
+    bin()
+        .arg("--timeline")
+        .arg("--csv")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--timeline only applies to the human view and --json",
+        ));
+
+    bin()
+        .arg("--timeline=day")
+        .arg("--tree")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--timeline cannot be combined with --grid, --tree, --long, --hints, --all-history, or --hash",
+        ));
+
+    bin()
+        .arg("--timeline")
+        .arg("--json")
+        .arg("--json-version")
+        .arg("2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--json-version cannot be combined with --timeline",
+        ));
+
+    bin()
+        .arg("--timeline=week")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'week'"));
+}
+
+#[test]
+fn heatmap_is_human_only_and_bounded_to_a_year() {
+    bin()
+        .arg("--heatmap")
+        .arg("--json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--heatmap only applies to the human view",
+        ));
+
+    bin()
+        .arg("--heatmap")
+        .arg("--timeline")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--heatmap cannot be combined with --grid, --tree, --timeline, --long, --hints, or --all-history",
+        ));
+
+    bin()
+        .arg("--heatmap=54")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("54 is not in 1..=53"));
+}
+
+#[test]
+fn summary_accepts_only_human_or_json_output() {
+    bin()
+        .arg("--summary")
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--summary only applies to the human view and --json",
+        ));
+
+    bin()
+        .arg("--summary")
+        .arg("--heatmap")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--summary cannot be combined with --grid, --tree, --timeline, --heatmap, --long, --hints, or --hash",
+        ));
+}
+
+#[test]
+fn limit_rejects_bad_specs_and_bucket_limits_outside_the_bucketed_view() {
+    bin()
+        .arg("--limit")
+        .arg("week=3")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown bucket `week`"));
+
+    bin()
+        .arg("-n")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the overall limit must be at least 1",
+        ));
+
+    bin()
+        .arg("--limit")
+        .arg("history=2")
+        .arg("--json")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "per-bucket --limit values only apply to the bucketed human view",
+        ));
+
+    bin()
+        .arg("--limit")
+        .arg("history=2")
+        .arg("--all-history")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--all-history cannot be combined with --limit history=N",
+        ));
+}
+
+#[test]
+fn theme_is_human_only_and_rejects_unknown_names() {
+    bin()
+        .arg("--theme")
+        .arg("mono")
+        .arg("--plain")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--theme only applies to human views",
+        ));
+
+    bin()
+        .arg("--theme")
+        .arg("dracula")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value for --theme: `dracula` is neither a built-in theme (default, mono, solarized, high-contrast) nor a theme file",
+        ));
+
+    bin()
+        .env("FTIME_THEME", "dracula")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value for FTIME_THEME"));
+
+    bin()
+        .env("FTIME_THEME", "dracula")
+        .arg("--json")
+        .assert()
+        .success();
+}
+
+#[test]
+fn icons_rejects_unknown_sets() {
+    bin()
+        .arg("--icons=sparkles")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'sparkles'"));
+}
+
+#[test]
+fn hyperlink_only_applies_to_the_bucketed_human_view() {
+    for args in [&["--plain"][..], &["--tree"], &["--summary"]] {
+        bin()
+            .args(args)
+            .arg("--hyperlink")
+            .arg("always")
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "--hyperlink only applies to the bucketed human view",
+            ));
+    }
+}
+
+#[test]
+fn exec_replaces_the_listing_and_rejects_other_outputs() {
+    bin()
+        .args(["--exec", "echo", ";", "--exec-batch", "echo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--exec and --exec-batch cannot be combined",
+        ));
+    bin()
+        .args(["--exec-batch", "echo", ";", "--json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--exec-batch cannot be combined with --json",
+        ));
+    bin()
+        .args(["--tree", "--exec", "echo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--exec cannot be combined with --grid, --tree",
+        ));
+    bin()
+        .args(["--exec", ""])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--exec needs a command to run"));
+}
+
+#[test]
+fn shell_subcommands_take_a_known_shell_and_no_scan_flags() {
+    bin()
+        .args(["completions", "tcsh"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'tcsh'"));
+    bin()
+        .args(["shell-init", "powershell"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'powershell'"));
+    bin()
+        .args(["completions", "bash", "--plain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '--plain'"));
+}
+
+#[test]
+fn picker_is_an_output_mode_that_keeps_color_and_theme() {
+    bin()
+        .args(["--picker", "--plain"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--plain and --picker cannot be combined",
+        ));
+    bin()
+        .args(["--picker", "--hints"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--picker cannot be combined with --all-history, --hints, or --icons",
+        ));
+    bin()
+        .args(["--picker", "--grid"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--grid only applies to the human view",
+        ));
+
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("f1"), b"x").unwrap();
+    bin()
+        .arg(dir.path())
+        .args(["--picker", "--color", "always", "--theme", "mono", "-A"])
+        .assert()
+        .success();
+    bin()
+        .arg(dir.path())
+        .args(["--picker", "--print0", "--color", "always", "-A"])
+        .assert()
+        .success();
+}
@@ -28,3 +28,62 @@ fn since_help_describes_lower_bound_filter() {
     assert!(stdout.contains("--since <SINCE>"));
     assert!(stdout.contains("Only show entries modified at or after the given lower bound"));
 }
+
+#[test]
+fn man_page_covers_every_flag_and_the_contract_sections() {
+    let output = bin().arg("man").output().unwrap();
+    assert!(output.status.success());
+
+    let page = String::from_utf8(output.stdout).unwrap();
+    assert!(page.contains(".TH FTIME 1"));
+    for section in [
+        ".SH OPTIONS",
+        ".SH COMMANDS",
+        ".SH \"VALIDATION RULES\"",
+        ".SH ENVIRONMENT",
+        ".SH \"EXIT STATUS\"",
+    ] {
+        assert!(page.contains(section), "missing {section}");
+    }
+
+    let help = String::from_utf8(bin().arg("--help").output().unwrap().stdout).unwrap();
+    let flags: Vec<&str> = help
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix("--"))
+        .map(|flag| flag.split(['[', '=', ',', '<']).next().unwrap())
+        .filter(|flag| {
+            !flag.is_empty()
+                && flag
+                    .chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch == '-' || ch.is_ascii_digit())
+        })
+        .collect();
+    assert!(flags.contains(&"picker"));
+    for flag in flags {
+        let roff = format!("\\fB\\-\\-{}", flag.replace('-', "\\-"));
+        assert!(
+            page.contains(&roff),
+            "--{flag} is missing from the man page"
+        );
+    }
+
+    let contract = std::fs::read_to_string("docs/CLI.md").unwrap();
+    for heading in contract.lines().filter_map(|line| line.strip_prefix("## ")) {
+        let (number, title) = heading.split_once(". ").unwrap();
+        if number == "1" || number == "3" {
+            continue;
+        }
+        let title = if number.chars().all(|ch| ch.is_ascii_digit()) {
+            title.to_uppercase()
+        } else {
+            title.to_string()
+        };
+        assert!(
+            page.contains(&format!(".SH {title}\n"))
+                || page.contains(&format!(".SH \"{title}\"\n"))
+                || page.contains(&format!(".SS {title}\n"))
+                || page.contains(&format!(".SS \"{title}\"\n")),
+            "section {heading} is missing from the man page"
+        );
+    }
+}
@@ -87,3 +87,25 @@ fn release_workflow_uses_gh_cli_instead_of_node_based_release_action() {
         ],
     );
 }
+
+#[test]
+fn release_workflow_requires_and_checks_the_signing_key_pair() {
+    let content = support::read_repo_file(".github/workflows/release.yml");
+
+    assert_contains_all(
+        &content,
+        ".github/workflows/release.yml",
+        &[
+            "name: Check release signing setup",
+            "FTIME_RELEASE_PUBLIC_KEY: ${{ vars.FTIME_RELEASE_PUBLIC_KEY }}",
+            "MINISIGN_SECRET_KEY: ${{ secrets.MINISIGN_SECRET_KEY }}",
+            "minisign -S -s ../minisign.key -m SHA256SUMS -x SHA256SUMS.minisig",
+            "minisign -V -P \"$FTIME_RELEASE_PUBLIC_KEY\" -m SHA256SUMS -x SHA256SUMS.minisig",
+        ],
+    );
+    assert_contains_none(
+        &content,
+        ".github/workflows/release.yml",
+        &["if [[ -n \"$MINISIGN_SECRET_KEY\" ]]"],
+    );
+}
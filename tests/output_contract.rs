@@ -6,6 +6,7 @@ use std::fs::File;
 use std::path::Path;
 use std::time::{Duration, SystemTime};
 use tempfile::tempdir;
+use unicode_width::UnicodeWidthStr;
 
 #[allow(deprecated)]
 fn bin() -> Command {
@@ -52,7 +53,9 @@ fn human_stdout(path: &Path, args: &[&str]) -> String {
     for arg in args {
         cmd.arg(arg);
     }
-    cmd.env("FTIME_FORCE_TTY", "1").env("NO_COLOR", "1");
+    cmd.env("FTIME_FORCE_TTY", "1")
+        .env("NO_COLOR", "1")
+        .env_remove("COLUMNS");
     stdout(cmd)
 }
 
@@ -320,3 +323,1240 @@ fn plain_absolute_keeps_tsv_shape_and_only_changes_time_column() {
     assert!(cols[2].starts_with("2023-"));
     assert!(cols[2].contains(" (UTC"));
 }
+
+#[test]
+fn hash_adds_a_plain_column_and_a_json_field_for_regular_files() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("abc.txt"), b"abc").unwrap();
+    fs::write(dir.path().join("big.bin"), vec![0u8; 4096]).unwrap();
+    fs::create_dir(dir.path().join("docs")).unwrap();
+
+    let plain = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--plain")
+            .arg("--hash")
+            .arg("sha256")
+            .arg("--hash-max-size")
+            .arg("1K");
+        cmd
+    });
+    let abc_cols: Vec<&str> = line_containing(&plain, "abc.txt").split('\t').collect();
+    assert_eq!(abc_cols.len(), 4);
+    assert_eq!(
+        abc_cols[3],
+        "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    let big_cols: Vec<&str> = line_containing(&plain, "big.bin").split('\t').collect();
+    assert_eq!(big_cols[3], "-");
+    let docs_cols: Vec<&str> = line_containing(&plain, "docs").split('\t').collect();
+    assert_eq!(docs_cols[3], "-");
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json").arg("--hash").arg("xxh3");
+        cmd
+    });
+    let line = line_containing(&json, "\"path\":\"abc.txt\"");
+    assert_json_key_order(line, &["path", "bucket", "size", "hash"]);
+    let value: Value = serde_json::from_str(line).unwrap();
+    assert_eq!(value["hash"], "xxh3:78af5f94892f3950");
+
+    let docs: Value = serde_json::from_str(line_containing(&json, "\"path\":\"docs\"")).unwrap();
+    assert!(docs.get("hash").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn long_mode_adds_aligned_unix_columns_and_optional_json_fields() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let script = dir.path().join("run.sh");
+    fs::write(&script, b"#!/bin/sh\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::create_dir(dir.path().join("docs")).unwrap();
+
+    let human = human_stdout(dir.path(), &["--long"]);
+    let script_line = line_containing(&human, "run.sh");
+    assert!(script_line.contains("-rwxr-xr-x"), "{script_line}");
+    let docs_line = line_containing(&human, "docs/");
+    assert!(docs_line.contains("drwxr-xr-x"), "{docs_line}");
+    assert_eq!(
+        script_line.find("run.sh"),
+        docs_line.find("docs/"),
+        "{human}"
+    );
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json").arg("-l");
+        cmd
+    });
+    let line = line_containing(&json, "\"path\":\"run.sh\"");
+    assert_json_key_order(
+        line,
+        &[
+            "path", "size", "mode", "nlink", "inode", "uid", "gid", "owner", "group",
+        ],
+    );
+    let value: Value = serde_json::from_str(line).unwrap();
+    assert_eq!(value["mode"], "0755");
+    assert_eq!(value["nlink"], 1);
+
+    let plain_json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json");
+        cmd
+    });
+    let value: Value =
+        serde_json::from_str(line_containing(&plain_json, "\"path\":\"run.sh\"")).unwrap();
+    assert!(value.get("mode").is_none());
+    assert!(value.get("owner").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn special_entry_kinds_get_dedicated_labels_sizes_and_json_kinds() {
+    use std::os::unix::fs::symlink;
+    use std::os::unix::net::UnixListener;
+
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("real_dir")).unwrap();
+    symlink(dir.path().join("real_dir"), dir.path().join("dir_link")).unwrap();
+    symlink(dir.path().join("missing"), dir.path().join("dangling")).unwrap();
+    let _listener = UnixListener::bind(dir.path().join("app.sock")).unwrap();
+
+    let human = human_stdout(dir.path(), &[]);
+    let dir_link = line_containing(&human, "dir_link");
+    assert!(dir_link.contains("[LND]"), "{dir_link}");
+    assert!(dir_link.contains("<lnk>"), "{dir_link}");
+    let dangling = line_containing(&human, "dangling");
+    assert!(dangling.contains("[BRK]"), "{dangling}");
+    let socket = line_containing(&human, "app.sock");
+    assert!(socket.contains("[SOC]"), "{socket}");
+    assert!(socket.contains("<sock>"), "{socket}");
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json");
+        cmd
+    });
+    let kind_of = |path: &str| {
+        let value: Value =
+            serde_json::from_str(line_containing(&json, &format!("\"path\":\"{path}\""))).unwrap();
+        value["kind"].as_str().unwrap().to_string()
+    };
+    assert_eq!(kind_of("real_dir"), "dir");
+    assert_eq!(kind_of("dir_link"), "symlink_dir");
+    assert_eq!(kind_of("dangling"), "broken_symlink");
+    assert_eq!(kind_of("app.sock"), "socket");
+
+    let socket_line = line_containing(&json, "\"path\":\"app.sock\"");
+    assert_json_key_order(socket_line, &["is_dir", "is_symlink", "kind"]);
+    assert!(!socket_line.contains("\"size\""), "{socket_line}");
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_reports_link_targets_and_counts_unresolved_links() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempdir().unwrap();
+    let real = dir.path().join("real_dir");
+    fs::create_dir(&real).unwrap();
+    symlink(&real, dir.path().join("farm_link")).unwrap();
+    symlink(dir.path(), dir.path().join("loop_link")).unwrap();
+
+    let human = human_stdout(dir.path(), &["--follow"]);
+    let farm = line_containing(&human, "farm_link");
+    assert!(farm.contains("[DIR]"), "{farm}");
+    assert!(farm.contains("<dir>"), "{farm}");
+    let looped = line_containing(&human, "loop_link");
+    assert!(looped.contains("[LND]"), "{looped}");
+    assert!(
+        human.contains("Could not follow 1 symlinks (broken or looping)"),
+        "{human}"
+    );
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json").arg("-L");
+        cmd
+    });
+    let value: Value =
+        serde_json::from_str(line_containing(&json, "\"path\":\"farm_link\"")).unwrap();
+    assert_eq!(value["kind"], "dir");
+    assert_eq!(value["is_symlink"], false);
+    assert!(value["symlink_target"].is_string());
+}
+
+#[test]
+fn format_template_renders_one_line_per_entry() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("alpha.txt");
+    fs::write(&file_path, b"hello").unwrap();
+    let fixed = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    set_file_mtime(&file_path, FileTime::from_system_time(fixed)).unwrap();
+    fs::create_dir(dir.path().join("docs")).unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--format")
+            .arg("{bucket}\\t{path}\\t{size}\\t{ext}\\t{kind}\\t{mtime_unix}");
+        cmd
+    });
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2, "{output}");
+    assert!(lines[0].starts_with("active\tdocs\t\t\tdir\t"), "{output}");
+    assert_eq!(lines[1], "history\talpha.txt\t5\ttxt\tfile\t1700000000");
+}
+
+#[cfg(unix)]
+#[test]
+fn format_values_cannot_split_or_forge_records() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("two\nlines"), b"x").unwrap();
+    fs::create_dir(dir.path().join("x\ndir\t\x1b[31m")).unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--format").arg("{kind}\\t{name}");
+        cmd
+    });
+    let mut lines: Vec<&str> = output.lines().collect();
+    lines.sort_unstable();
+    assert_eq!(
+        lines,
+        ["dir\tx\\ndir\\t\\x1b[31m", "file\ttwo\\nlines"],
+        "{output}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn csv_tsv_and_print0_keep_awkward_names_intact() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("a,\"b\".txt"), b"hi").unwrap();
+    fs::write(dir.path().join("tab\there.txt"), b"").unwrap();
+
+    let csv = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--csv");
+        cmd
+    });
+    let mut records = csv.split("\r\n");
+    assert_eq!(
+        records.next(),
+        Some("path,bucket,mtime,relative_time,kind,size")
+    );
+    let quoted = line_containing(&csv, "\"a,\"\"b\"\".txt\"");
+    assert!(quoted.ends_with(",file,2"), "{quoted:?}");
+
+    let tsv = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--tsv")
+            .arg("--columns")
+            .arg("name,size")
+            .arg("--no-header");
+        cmd
+    });
+    let lines: Vec<&str> = tsv.lines().collect();
+    assert_eq!(lines.len(), 2, "{tsv}");
+    assert!(lines.contains(&"tab\\there.txt\t0"), "{tsv}");
+    assert!(lines.contains(&"a,\"b\".txt\t2"), "{tsv}");
+
+    let print0 = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--print0");
+        cmd
+    });
+    let mut paths: Vec<&str> = print0.split_terminator('\0').collect();
+    paths.sort();
+    assert_eq!(paths, vec!["a,\"b\".txt", "tab\there.txt"]);
+}
+
+#[test]
+fn json_doc_emits_one_document_with_scan_metadata_and_buckets() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("recent.txt"), b"hi").unwrap();
+    let old = dir.path().join("old.md");
+    fs::write(&old, b"old").unwrap();
+    set_file_mtime(
+        &old,
+        FileTime::from_system_time(SystemTime::now() - Duration::from_secs(30 * 24 * 3600)),
+    )
+    .unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--json-doc")
+            .arg("--ext")
+            .arg("txt,md");
+        cmd
+    });
+    let doc: Value = serde_json::from_str(&output).unwrap();
+    assert_json_key_order(
+        &output,
+        &[
+            "schema_version",
+            "root",
+            "scanned_at",
+            "filters",
+            "stats",
+            "buckets",
+        ],
+    );
+    assert_eq!(doc["schema_version"], 1);
+    assert_eq!(
+        doc["root"],
+        dir.path().canonicalize().unwrap().display().to_string()
+    );
+    assert_eq!(doc["filters"]["ext"], serde_json::json!(["txt", "md"]));
+    assert_eq!(doc["filters"]["dots"], "default");
+    assert_eq!(doc["stats"]["visible_entries"], 2);
+
+    let buckets = &doc["buckets"];
+    assert_eq!(buckets["active"][0]["path"], "recent.txt");
+    assert_eq!(buckets["active"][0]["size"], 2);
+    assert_eq!(buckets["today"], serde_json::json!([]));
+    assert_eq!(buckets["this_week"], serde_json::json!([]));
+    assert_eq!(buckets["history"][0]["path"], "old.md");
+}
+
+fn schema(version: &str) -> jsonschema::Validator {
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg("schema")
+            .arg("--json")
+            .arg("--json-version")
+            .arg(version);
+        cmd
+    });
+    let schema: Value = serde_json::from_str(&output).unwrap();
+    jsonschema::validator_for(&schema).unwrap()
+}
+
+fn assert_lines_match_schema(validator: &jsonschema::Validator, json: &str) {
+    for line in json.lines() {
+        let value: Value = serde_json::from_str(line).unwrap();
+        if let Err(err) = validator.validate(&value) {
+            panic!("{line} does not match the schema: {err}");
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn json_lines_output_matches_the_published_schema_for_every_version() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("abc.txt"), b"abc").unwrap();
+    fs::create_dir(dir.path().join("docs")).unwrap();
+    create_file_symlink(&dir.path().join("abc.txt"), &dir.path().join("link"));
+    create_file_symlink(&dir.path().join("missing"), &dir.path().join("dangling"));
+
+    let v1 = schema("1");
+    let v2 = schema("2");
+
+    let full_v1 = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--json")
+            .arg("--long")
+            .arg("--hash")
+            .arg("sha256");
+        cmd
+    });
+    assert_lines_match_schema(&v1, &full_v1);
+
+    let full_v2 = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--json")
+            .arg("--json-version")
+            .arg("2")
+            .arg("--long")
+            .arg("--hash")
+            .arg("sha256");
+        cmd
+    });
+    assert_lines_match_schema(&v2, &full_v2);
+    let first = full_v2.lines().next().unwrap();
+    assert!(
+        first.starts_with("{\"schema_version\":2,\"path\":"),
+        "{first}"
+    );
+
+    let unversioned: Value = serde_json::from_str(full_v1.lines().next().unwrap()).unwrap();
+    assert!(v2.validate(&unversioned).is_err());
+    let versioned: Value = serde_json::from_str(first).unwrap();
+    assert!(v1.validate(&versioned).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn non_utf8_names_are_escaped_or_passed_through_losslessly() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(OsStr::from_bytes(b"caf\xe9.txt")), b"x").unwrap();
+
+    let human = human_stdout(dir.path(), &[]);
+    assert!(human.contains("caf\\xe9.txt"), "{human}");
+    assert!(!human.contains('\u{fffd}'), "{human}");
+
+    let plain = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--plain");
+        cmd
+    });
+    assert!(plain.starts_with("caf\\xe9.txt\t"), "{plain}");
+
+    let raw = bin()
+        .arg(dir.path())
+        .arg("--plain")
+        .arg("--raw-bytes")
+        .output()
+        .unwrap();
+    assert!(raw.status.success());
+    assert!(raw.stdout.starts_with(b"caf\xe9.txt\t"));
+
+    let print0 = bin().arg(dir.path()).arg("--print0").output().unwrap();
+    assert_eq!(print0.stdout, b"caf\xe9.txt\0");
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--json");
+        cmd
+    });
+    let line = json.lines().next().unwrap();
+    assert_json_key_order(line, &["path", "path_bytes", "bucket"]);
+    let value: Value = serde_json::from_str(line).unwrap();
+    assert_eq!(value["path"], "caf\\xe9.txt");
+    assert_eq!(value["path_bytes"], "Y2Fm6S50eHQ=");
+}
+
+#[cfg(unix)]
+#[test]
+fn backslash_names_are_escaped_once_by_each_writer() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("a\\b"), b"x").unwrap();
+
+    let output = |args: &[&str]| {
+        stdout({
+            let mut cmd = bin();
+            cmd.arg(dir.path()).args(args);
+            cmd
+        })
+    };
+
+    let plain = output(&["--plain"]);
+    assert!(plain.starts_with("a\\b\t"), "{plain}");
+
+    let csv = output(&["--csv", "--columns", "path", "--no-header"]);
+    assert_eq!(csv, "a\\b\r\n");
+
+    let tsv = output(&["--tsv", "--columns", "path", "--no-header"]);
+    assert_eq!(tsv, "a\\\\b\n");
+
+    let json = output(&["--json"]);
+    let value: Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+    assert_eq!(value["path"], "a\\b");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn backslashes_are_doubled_in_names_with_invalid_bytes() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join(OsStr::from_bytes(b"c\\\xff")), b"x").unwrap();
+
+    let plain = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--plain");
+        cmd
+    });
+    assert!(plain.starts_with("c\\\\\\xff\t"), "{plain}");
+}
+
+#[cfg(unix)]
+#[test]
+fn human_view_neutralizes_control_characters_and_quoting_style_applies_to_plain() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("evil\x1b[2Jname"), b"").unwrap();
+    fs::write(dir.path().join("it's here"), b"").unwrap();
+
+    let human = human_stdout(dir.path(), &[]);
+    assert!(!human.contains('\x1b'), "{human:?}");
+    assert!(human.contains("evil\\x1b[2Jname"), "{human}");
+    assert!(human.contains("it's here"), "{human}");
+
+    let shell = human_stdout(dir.path(), &["--quoting-style", "shell"]);
+    assert!(shell.contains("$'evil\\x1b[2Jname'"), "{shell}");
+    assert!(shell.contains("'it'\\''s here'"), "{shell}");
+
+    let plain = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--plain");
+        cmd
+    });
+    assert!(plain.contains("evil\x1b[2Jname\t"), "{plain:?}");
+
+    let plain_c = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--plain")
+            .arg("--quoting-style")
+            .arg("c");
+        cmd
+    });
+    assert!(plain_c.contains("\"evil\\x1b[2Jname\"\t"), "{plain_c}");
+    assert!(plain_c.contains("\"it's here\"\t"), "{plain_c}");
+}
+
+#[test]
+fn human_name_column_follows_columns_env_and_truncate_mode() {
+    let dir = tempdir().unwrap();
+    let name = "a-rather-long-quarterly-report-draft-final.pdf";
+    fs::write(dir.path().join(name), b"x").unwrap();
+
+    let human_with_columns = |columns: &str, extra: &[&str]| {
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .args(extra)
+            .env("NO_COLOR", "1")
+            .env("COLUMNS", columns);
+        stdout(cmd)
+    };
+
+    let wide = human_with_columns("120", &[]);
+    assert!(wide.contains(name), "{wide}");
+
+    let fixed = human_stdout(dir.path(), &[]);
+    assert!(fixed.contains("a-rather-long-quarterly~.pdf"), "{fixed}");
+
+    let narrow = human_with_columns("40", &[]);
+    let row = line_containing(&narrow, "[FIL]");
+    assert!(row.contains("~.pdf"), "{row}");
+    assert!(row.len() <= 40, "{row}");
+
+    let middle = human_with_columns("40", &["--truncate", "middle"]);
+    let row = line_containing(&middle, "[FIL]");
+    assert!(row.contains("a-rather-"), "{row}");
+    assert!(row.contains("~"), "{row}");
+    assert!(row.contains("final.pdf"), "{row}");
+    assert!(row.len() <= 40, "{row}");
+}
+
+#[test]
+fn grid_lays_out_names_in_columns_under_bucket_headers() {
+    let dir = tempdir().unwrap();
+    for i in 0..6 {
+        fs::write(dir.path().join(format!("note-{i}.md")), b"x").unwrap();
+    }
+    fs::create_dir(dir.path().join("docs")).unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--grid")
+            .env("NO_COLOR", "1")
+            .env("COLUMNS", "40");
+        cmd
+    });
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "Active (7)", "{output}");
+    let grid_rows: Vec<&str> = lines[1..]
+        .iter()
+        .copied()
+        .take_while(|line| !line.is_empty())
+        .collect();
+    assert!(grid_rows.len() < 7, "{output}");
+    assert!(grid_rows.iter().all(|row| row.len() <= 40), "{output}");
+    assert!(output.contains("docs/"), "{output}");
+    assert!(!output.contains("[FIL]"), "{output}");
+    assert!(!output.contains("<dir>"), "{output}");
+}
+
+#[test]
+fn tree_expands_recent_directories_and_collapses_old_branches() {
+    let dir = tempdir().unwrap();
+    let old = FileTime::from_system_time(SystemTime::now() - Duration::from_secs(30 * 86400));
+    fs::write(dir.path().join("notes.md"), b"x").unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src").join("main.rs"), b"x").unwrap();
+    fs::create_dir(dir.path().join("archive")).unwrap();
+    fs::write(dir.path().join("archive").join("old.txt"), b"x").unwrap();
+    set_file_mtime(dir.path().join("archive").join("old.txt"), old).unwrap();
+    set_file_mtime(dir.path().join("archive"), old).unwrap();
+    set_file_mtime(dir.path().join("src"), old).unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--tree").env("NO_COLOR", "1");
+        cmd
+    });
+    let src = line_containing(&output, "src/");
+    assert!(src.starts_with("├── "), "{output}");
+    let child = line_containing(&output, "main.rs");
+    assert!(child.starts_with("│   └── "), "{output}");
+    assert!(
+        line_containing(&output, "notes.md").starts_with("├── "),
+        "{output}"
+    );
+    assert!(!output.contains("archive"), "{output}");
+    assert!(output.contains("└── … 1 older entries"), "{output}");
+}
+
+#[cfg(unix)]
+#[test]
+fn tree_marks_unreadable_directories_and_counts_them_in_the_footer() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let locked = dir.path().join("locked");
+    fs::create_dir(&locked).unwrap();
+    fs::write(locked.join("secret.txt"), b"x").unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    // Privileged users can read the directory anyway.
+    let readable = fs::read_dir(&locked).is_ok();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--tree").env("NO_COLOR", "1");
+        cmd
+    });
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    if readable {
+        return;
+    }
+
+    let line = line_containing(&output, "locked/");
+    assert!(line.ends_with("[unreadable]"), "{output}");
+    assert!(!output.contains("secret.txt"), "{output}");
+    assert!(output.contains("Skipped 1 unreadable entries"), "{output}");
+}
+
+#[test]
+fn timeline_bins_modification_counts_for_human_and_json() {
+    let dir = tempdir().unwrap();
+    let now = SystemTime::now();
+    fs::write(dir.path().join("fresh-a.txt"), b"a").unwrap();
+    fs::write(dir.path().join("fresh-b.txt"), b"b").unwrap();
+    let ancient = dir.path().join("ancient.txt");
+    fs::write(&ancient, b"c").unwrap();
+    set_file_mtime(
+        &ancient,
+        FileTime::from_system_time(now - Duration::from_secs(90 * 86400)),
+    )
+    .unwrap();
+
+    let human = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--timeline").env("NO_COLOR", "1");
+        cmd
+    });
+    let lines: Vec<&str> = human.lines().collect();
+    assert_eq!(lines[0], "Timeline (last 30 days)", "{human}");
+    assert!(lines[30].ends_with("█ 2"), "{human}");
+    assert!(lines[1].ends_with(" 0"), "{human}");
+    assert!(human.contains("1 earlier entries not shown"), "{human}");
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--timeline=hour").arg("--json");
+        cmd
+    });
+    assert_eq!(json.lines().count(), 1, "{json}");
+    let value: Value = serde_json::from_str(json.trim()).unwrap();
+    assert_eq!(value["bin"], "hour");
+    assert_eq!(value["bins"].as_array().unwrap().len(), 24);
+    assert_eq!(value["bins"][23]["count"], 2);
+    let first_start = value["bins"][0]["start"].as_str().unwrap();
+    assert!(first_start.ends_with("+00:00"), "{first_start}");
+    assert_eq!(value["earlier"], 1);
+    assert_eq!(value["total"], 3);
+}
+
+#[test]
+fn heatmap_draws_weekday_rows_with_monochrome_glyphs() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("today.txt"), b"a").unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--heatmap=4").env("NO_COLOR", "1");
+        cmd
+    });
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "Activity (last 4 weeks, 1 entries)", "{output}");
+    let weekdays: Vec<&str> = lines[2..9].iter().map(|line| &line[..3]).collect();
+    assert_eq!(
+        weekdays,
+        ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        "{output}"
+    );
+    assert_eq!(output.matches('█').count(), 2, "{output}");
+    assert!(lines[9].contains("Less · ░ ▒ ▓ █ More"), "{output}");
+    assert!(!output.contains('\u{1b}'), "{output}");
+}
+
+#[test]
+fn summary_tables_reconcile_with_the_scan_in_human_and_json() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.rs"), b"fn main() {}").unwrap();
+    fs::write(dir.path().join("lib.RS"), b"pub fn f() {}").unwrap();
+    fs::write(dir.path().join("README"), b"hi").unwrap();
+    fs::create_dir(dir.path().join("docs")).unwrap();
+    fs::write(dir.path().join("docs").join("guide.md"), b"guide").unwrap();
+
+    let human = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--summary").env("NO_COLOR", "1");
+        cmd
+    });
+    assert!(human.starts_with("Summary (4 entries, 27 B)"), "{human}");
+    let rs = line_containing(&human, ".rs ");
+    assert_eq!(
+        rs.split_whitespace().collect::<Vec<_>>(),
+        [".rs", "2", "0", "0", "0", "2", "25", "B"],
+        "{human}"
+    );
+    assert!(line_containing(&human, "(none)").contains(" 1 "), "{human}");
+    let docs = line_containing(&human, "docs/");
+    assert!(docs.trim_end().ends_with("5 B"), "{human}");
+
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--summary").arg("--json");
+        cmd
+    });
+    let value: Value = serde_json::from_str(json.trim()).unwrap();
+    assert_eq!(value["total"]["count"], 4);
+    // `stats` also covers the read of `docs/` for its directory row.
+    assert_eq!(value["stats"]["visible_entries"], 5);
+    assert_eq!(value["buckets"]["active"]["count"], 4);
+    let extensions = value["extensions"].as_array().unwrap();
+    let counted: u64 = extensions
+        .iter()
+        .map(|row| row["total"]["count"].as_u64().unwrap())
+        .sum();
+    assert_eq!(counted, 4);
+    assert_eq!(extensions[0]["ext"], ".rs");
+    assert_eq!(value["directories"][0]["path"], "./");
+    assert_eq!(value["directories"][1]["path"], "docs/");
+    assert_eq!(value["directories"][1]["total"]["bytes"], 5);
+}
+
+#[cfg(unix)]
+#[test]
+fn summary_marks_unreadable_directories_and_counts_them_in_the_footer() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.rs"), b"fn main() {}").unwrap();
+    let locked = dir.path().join("locked");
+    fs::create_dir(&locked).unwrap();
+    fs::write(locked.join("secret.txt"), b"x").unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    // Privileged users can read the directory anyway.
+    let readable = fs::read_dir(&locked).is_ok();
+
+    let human = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--summary").env("NO_COLOR", "1");
+        cmd
+    });
+    let json = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--summary").arg("--json");
+        cmd
+    });
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    if readable {
+        return;
+    }
+
+    let line = line_containing(&human, "locked/");
+    assert!(line.ends_with("[unreadable]"), "{human}");
+    assert!(human.contains("Skipped 1 unreadable entries"), "{human}");
+
+    let value: Value = serde_json::from_str(json.trim()).unwrap();
+    let locked_row = value["directories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["path"] == "locked/")
+        .unwrap();
+    assert_eq!(locked_row["unreadable"], true);
+    assert_eq!(locked_row["total"]["count"], 0);
+    assert_eq!(value["directories"][0].get("unreadable"), None);
+    assert_eq!(value["stats"]["skipped_unreadable"], 1);
+}
+
+#[test]
+fn limit_caps_newest_entries_and_sets_bucket_previews() {
+    let dir = tempdir().unwrap();
+    let now = SystemTime::now();
+    for i in 0..4 {
+        let path = dir.path().join(format!("new-{i}.txt"));
+        fs::write(&path, b"x").unwrap();
+        set_file_mtime(
+            &path,
+            FileTime::from_system_time(now - Duration::from_secs(60 * (i + 1))),
+        )
+        .unwrap();
+    }
+    for i in 0..3 {
+        let path = dir.path().join(format!("old-{i}.txt"));
+        fs::write(&path, b"x").unwrap();
+        set_file_mtime(
+            &path,
+            FileTime::from_system_time(now - Duration::from_secs(30 * 86400)),
+        )
+        .unwrap();
+    }
+
+    let plain = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--plain").arg("-n").arg("2");
+        cmd
+    });
+    let paths: Vec<&str> = plain
+        .lines()
+        .map(|line| line.split('\t').next().unwrap())
+        .collect();
+    assert_eq!(paths, ["new-0.txt", "new-1.txt"]);
+
+    let capped = human_stdout(dir.path(), &["--limit", "5"]);
+    assert!(capped.contains("Active (4)"), "{capped}");
+    assert!(capped.contains("History (1/3)"), "{capped}");
+
+    let spent = human_stdout(dir.path(), &["-n", "2"]);
+    assert!(spent.contains("Active (2/4)"), "{spent}");
+    assert!(!spent.contains("History"), "{spent}");
+    assert!(
+        spent.contains("… 3 more in older buckets (raise -n to show them)"),
+        "{spent}"
+    );
+
+    let previews = human_stdout(dir.path(), &["--limit", "active=1,history=0"]);
+    assert!(previews.contains("Active (1/4)"), "{previews}");
+    assert!(previews.contains("new-0.txt"), "{previews}");
+    assert!(!previews.contains("new-1.txt"), "{previews}");
+    assert!(previews.contains("History (0/3)"), "{previews}");
+    assert!(!previews.contains("old-"), "{previews}");
+}
+
+#[test]
+fn theme_flag_and_env_restyle_the_human_view() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("fresh.txt"), b"x").unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+
+    let colored = |args: &[&str], theme_env: Option<&Path>| {
+        stdout({
+            let mut cmd = bin();
+            cmd.arg(dir.path())
+                .args(args)
+                .arg("--color")
+                .arg("always")
+                .env_remove("FTIME_THEME")
+                .env_remove("LS_COLORS");
+            if let Some(path) = theme_env {
+                cmd.env("FTIME_THEME", path);
+            }
+            cmd
+        })
+    };
+
+    let default = colored(&[], None);
+    assert!(default.contains("\u{1b}[1;32mActive (2)"), "{default}");
+
+    let mono = colored(&["--theme", "mono"], None);
+    assert!(mono.contains("\u{1b}[1mActive (2)"), "{mono}");
+    assert!(!mono.contains("\u{1b}[1;32m"), "{mono}");
+    assert!(!mono.contains("\u{1b}[1;34m"), "{mono}");
+
+    let theme_file = dir.path().join("retro.theme");
+    fs::write(
+        &theme_file,
+        "base = mono\nheader.active = red\nls_colors = false\n",
+    )
+    .unwrap();
+    let from_env = colored(&[], Some(&theme_file));
+    assert!(from_env.contains("\u{1b}[31mActive (3)"), "{from_env}");
+    let flag_wins = colored(&["--theme", "default"], Some(&theme_file));
+    assert!(flag_wins.contains("\u{1b}[1;32mActive (3)"), "{flag_wins}");
+}
+
+#[test]
+fn theme_can_opt_into_ls_colors_for_names() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.rs"), b"x").unwrap();
+    fs::write(dir.path().join("my lib.rs"), b"x").unwrap();
+    let theme_file = dir.path().join("ls.theme");
+    fs::write(&theme_file, "ls_colors = true\n").unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--theme")
+            .arg(&theme_file)
+            .arg("--color")
+            .arg("always")
+            .arg("--quoting-style")
+            .arg("shell")
+            .env("LS_COLORS", "di=01;34:*.rs=01;33");
+        cmd
+    });
+    assert!(output.contains("\u{1b}[1;33mmain.rs"), "{output}");
+    // The extension comes from the file name, not the quoted display text.
+    assert!(output.contains("\u{1b}[1;33m'my lib.rs'"), "{output}");
+}
+
+#[test]
+fn icons_prefix_each_entry_and_keep_names_aligned() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("main.rs"), b"fn main() {}").unwrap();
+    fs::write(dir.path().join("notes.txt"), b"x").unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+
+    let ascii = human_stdout(dir.path(), &["--icons=ascii"]);
+    assert!(ascii.contains("[DIR]  / src/"), "{ascii}");
+    assert!(ascii.contains("[FIL]  - main.rs"), "{ascii}");
+
+    let emoji = human_stdout(dir.path(), &["--icons=emoji"]);
+    let name_columns: Vec<usize> = ["main.rs", "notes.txt", "src/"]
+        .iter()
+        .map(|name| {
+            let line = line_containing(&emoji, name);
+            let prefix = &line[..line.find(name).unwrap()];
+            UnicodeWidthStr::width(prefix)
+        })
+        .collect();
+    assert_eq!(name_columns, [12, 12, 12], "{emoji}");
+    assert!(line_containing(&emoji, "main.rs").contains("🦀 main.rs"));
+    assert!(line_containing(&emoji, "src/").contains("📁 src/"));
+}
+
+#[test]
+fn hyperlink_always_wraps_names_without_shifting_columns() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("a b.txt"), b"x").unwrap();
+    fs::write(dir.path().join("longer-name.txt"), b"xy").unwrap();
+
+    let plain = human_stdout(dir.path(), &["--hyperlink", "never"]);
+    let linked = human_stdout(dir.path(), &["--hyperlink", "always"]);
+    assert!(!plain.contains("\u{1b}]8;;"), "{plain}");
+    assert!(
+        linked.contains("a%20b.txt\u{1b}\\a b.txt\u{1b}]8;;\u{1b}\\"),
+        "{linked}"
+    );
+
+    let strip = |text: &str| {
+        let mut out = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("\u{1b}]8;;") {
+            out.push_str(&rest[..start]);
+            let end = rest[start..].find("\u{1b}\\").unwrap();
+            rest = &rest[start + end + 2..];
+        }
+        out.push_str(rest);
+        out
+    };
+    assert_eq!(strip(&linked), plain);
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_runs_per_entry_in_listing_order_and_combines_status() {
+    let dir = tempdir().unwrap();
+    let now = SystemTime::now();
+    for (index, name) in ["c.txt", "b.txt", "a.txt"].into_iter().enumerate() {
+        let path = dir.path().join(name);
+        fs::write(&path, name).unwrap();
+        let mtime = now - Duration::from_secs(60 * (index as u64 + 1));
+        set_file_mtime(path, FileTime::from_system_time(mtime)).unwrap();
+    }
+
+    let each = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).args([
+            "--exec",
+            "sh",
+            "-c",
+            "echo \"$1\" \"$2\"",
+            "sh",
+            "{/}",
+            "{/.}",
+        ]);
+        cmd
+    });
+    assert_eq!(each, "c.txt c\nb.txt b\na.txt a\n");
+
+    let batch = stdout({
+        let mut cmd = bin();
+        cmd.args(["-n", "2", "--exec-batch", "echo", "{/}", ";"])
+            .arg(dir.path());
+        cmd
+    });
+    assert_eq!(batch, "c.txt b.txt\n");
+
+    bin()
+        .arg(dir.path())
+        .args(["--exec", "sh", "-c", "test \"$1\" != b.txt", "sh", "{/}"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--exec: 1 of 3 commands failed"));
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_batch_splits_entries_that_exceed_the_command_line_limit() {
+    let dir = tempdir().unwrap();
+    let padding = "x".repeat(200);
+    for i in 0..1000 {
+        fs::write(dir.path().join(format!("{i:04}-{padding}")), b"").unwrap();
+    }
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .args(["--exec-batch", "sh", "-c", "echo $#", "sh", "{}"]);
+        cmd
+    });
+    let counts: Vec<usize> = output.lines().map(|line| line.parse().unwrap()).collect();
+    assert!(counts.len() > 1, "{output}");
+    assert_eq!(counts.iter().sum::<usize>(), 1000, "{output}");
+}
+
+#[test]
+fn completions_are_generated_from_the_cli_definition() {
+    for (shell, marker) in [
+        ("bash", "_ftime()"),
+        ("zsh", "#compdef ftime"),
+        ("fish", "complete -c ftime"),
+        ("powershell", "Register-ArgumentCompleter"),
+        ("elvish", "edit:completion:arg-completer[ftime]"),
+    ] {
+        let script = stdout({
+            let mut cmd = bin();
+            cmd.args(["completions", shell]);
+            cmd
+        });
+        assert!(script.contains(marker), "{shell}:\n{script}");
+        assert!(script.contains("exec-batch"), "{shell}:\n{script}");
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn shell_init_fcd_jumps_into_the_newest_subdirectory() {
+    if std::process::Command::new("bash")
+        .arg("-c")
+        .arg("true")
+        .status()
+        .is_err()
+    {
+        return;
+    }
+
+    let dir = tempdir().unwrap();
+    fs::create_dir(dir.path().join("older")).unwrap();
+    fs::create_dir(dir.path().join("newer\tdir")).unwrap();
+    // A file whose name looks like a type field must not be picked.
+    fs::write(dir.path().join("newest\t[DIR]"), b"x").unwrap();
+    let now = SystemTime::now();
+    for (name, age) in [("older", 7200), ("newer\tdir", 60), ("newest\t[DIR]", 0)] {
+        let mtime = now - Duration::from_secs(age);
+        set_file_mtime(dir.path().join(name), FileTime::from_system_time(mtime)).unwrap();
+    }
+
+    #[allow(deprecated)]
+    let exe = assert_cmd::cargo::cargo_bin("ftime");
+    let mut dirs = vec![exe.parent().unwrap().to_path_buf()];
+    dirs.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    let path = std::env::join_paths(dirs).unwrap();
+    let output = std::process::Command::new("bash")
+        .arg("-c")
+        .arg("eval \"$(ftime shell-init bash)\"; cd \"$1\" && fcd && pwd")
+        .arg("bash")
+        .arg(dir.path())
+        .env("PATH", path)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let pwd = String::from_utf8(output.stdout).unwrap();
+    assert!(pwd.trim_end().ends_with("/newer\tdir"), "{pwd}");
+}
+
+#[cfg(unix)]
+#[test]
+fn shell_init_picker_inserts_names_with_tabs_and_newlines_intact() {
+    use std::os::unix::fs::PermissionsExt;
+
+    if std::process::Command::new("bash")
+        .arg("-c")
+        .arg("true")
+        .status()
+        .is_err()
+    {
+        return;
+    }
+
+    let dir = tempdir().unwrap();
+    for name in ["two\nlines", "tab\there", "back\\slash"] {
+        fs::write(dir.path().join(name), b"x").unwrap();
+    }
+    // A stand-in fzf that selects every record.
+    let stub = tempdir().unwrap();
+    let fzf = stub.path().join("fzf");
+    fs::write(&fzf, "#!/bin/sh\ncat\n").unwrap();
+    fs::set_permissions(&fzf, fs::Permissions::from_mode(0o755)).unwrap();
+
+    #[allow(deprecated)]
+    let exe = assert_cmd::cargo::cargo_bin("ftime");
+    let mut dirs = vec![
+        stub.path().to_path_buf(),
+        exe.parent().unwrap().to_path_buf(),
+    ];
+    dirs.extend(std::env::split_paths(
+        &std::env::var_os("PATH").unwrap_or_default(),
+    ));
+    let path = std::env::join_paths(dirs).unwrap();
+    let output = std::process::Command::new("bash")
+        .arg("-c")
+        .arg(concat!(
+            "eval \"$(ftime shell-init bash)\"; cd \"$1\" || exit 1; ",
+            "READLINE_LINE=''; READLINE_POINT=0; __ftime_select; ",
+            "eval \"set -- $READLINE_LINE\"; ",
+            "for picked; do test -e \"$picked\" || exit 2; printf '%s\\0' \"$picked\"; done",
+        ))
+        .arg("bash")
+        .arg(dir.path())
+        .env("PATH", path)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let mut picked: Vec<&[u8]> = output
+        .stdout
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .collect();
+    picked.sort_unstable();
+    let base = std::path::absolute(dir.path()).unwrap();
+    let expected: Vec<Vec<u8>> = ["back\\slash", "tab\there", "two\nlines"]
+        .iter()
+        .map(|name| base.join(name).into_os_string().into_encoded_bytes())
+        .collect();
+    assert_eq!(picked, expected, "{output:?}");
+}
+
+#[test]
+fn picker_lines_lead_with_the_absolute_path_and_align_display_fields() {
+    let dir = tempdir().unwrap();
+    let now = SystemTime::now();
+    for (name, age) in [("fresh.txt", 0), ("old.txt", 90 * 86_400)] {
+        let path = dir.path().join(name);
+        fs::write(&path, b"x").unwrap();
+        let mtime = now - Duration::from_secs(age);
+        set_file_mtime(path, FileTime::from_system_time(mtime)).unwrap();
+    }
+    fs::create_dir(dir.path().join("src")).unwrap();
+
+    let output = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path())
+            .arg("--picker")
+            .arg("--color")
+            .arg("never");
+        cmd
+    });
+    let rows: Vec<Vec<&str>> = output
+        .lines()
+        .map(|line| line.split('\t').collect())
+        .collect();
+    assert_eq!(rows.len(), 3, "{output}");
+    for row in &rows {
+        assert_eq!(row.len(), 4, "{output}");
+        assert!(Path::new(row[0]).is_absolute(), "{output}");
+        assert_eq!(row[1].len(), rows[0][1].len(), "{output}");
+    }
+    let old = rows.iter().find(|row| row[3] == "old.txt").unwrap();
+    assert_eq!(Path::new(old[0]), dir.path().join("old.txt"));
+    assert_eq!(old[2], "[FIL]");
+    assert!(rows.iter().any(|row| row[3] == "src/" && row[2] == "[DIR]"));
+
+    let colored = stdout({
+        let mut cmd = bin();
+        cmd.arg(dir.path()).arg("--picker").env_remove("NO_COLOR");
+        cmd
+    });
+    assert!(colored.contains("\u{1b}["), "{colored}");
+}
+
+#[cfg(unix)]
+#[test]
+fn picker_print0_keeps_the_path_field_exact() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("two\nlines"), b"x").unwrap();
+
+    let output = bin()
+        .arg(dir.path())
+        .args(["--picker", "--print0", "--color", "never"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let record = output.stdout.strip_suffix(b"\0").unwrap();
+    let fields: Vec<&[u8]> = record.split(|byte| *byte == b'\t').collect();
+    assert_eq!(fields.len(), 4, "{output:?}");
+    let expected = std::path::absolute(dir.path().join("two\nlines")).unwrap();
+    assert_eq!(fields[0], expected.as_os_str().as_encoded_bytes());
+    assert_eq!(fields[3], b"two\\nlines");
+}
+
+#[test]
+fn preview_prints_a_metadata_card() {
+    let dir = tempdir().unwrap();
+    fs::write(dir.path().join("notes.md"), vec![b'x'; 2048]).unwrap();
+    fs::create_dir(dir.path().join("src")).unwrap();
+    fs::write(dir.path().join("src").join("main.rs"), b"fn main() {}").unwrap();
+
+    let card = |path: &Path| {
+        stdout({
+            let mut cmd = bin();
+            cmd.arg("preview").arg(path).arg("--color").arg("never");
+            cmd
+        })
+    };
+
+    let file = card(&dir.path().join("notes.md"));
+    assert!(file.starts_with("notes.md\n"), "{file}");
+    assert!(file.contains("kind      file\n"), "{file}");
+    assert!(file.contains("size      2.0 KiB (2048 bytes)\n"), "{file}");
+    assert!(file.contains("bucket    Active\n"), "{file}");
+
+    let folder = card(&dir.path().join("src"));
+    assert!(folder.contains("kind      dir\n"), "{folder}");
+    assert!(folder.contains("entries   1\n"), "{folder}");
+    assert!(
+        line_containing(&folder, "newest").contains("main.rs"),
+        "{folder}"
+    );
+
+    bin()
+        .arg("preview")
+        .arg(dir.path().join("missing"))
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("failed to read metadata for"));
+}
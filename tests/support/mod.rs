@@ -48,3 +48,81 @@ pub fn package_version() -> &'static str {
 pub fn release_tag() -> String {
     format!("v{}", package_version())
 }
+
+/// A release directory like the GitHub Releases assets of `version`: the
+/// platform archive holding a stand-in `ftime` script, plus `SHA256SUMS`.
+#[cfg(unix)]
+#[allow(dead_code)]
+pub fn fake_release(root: &Path, version: &str) -> std::path::PathBuf {
+    use sha2::{Digest, Sha256};
+    use std::os::unix::fs::PermissionsExt;
+
+    let build = root.join("build");
+    fs::create_dir_all(&build).unwrap();
+    let binary = build.join("ftime");
+    fs::write(&binary, format!("#!/bin/sh\nprintf 'ftime {version}\\n'\n")).unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let release = root.join("release");
+    fs::create_dir_all(&release).unwrap();
+    let archive = release_archive_name(version);
+    let status = std::process::Command::new("tar")
+        .arg("-C")
+        .arg(&build)
+        .arg("-czf")
+        .arg(release.join(&archive))
+        .arg("ftime")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let digest = Sha256::digest(fs::read(release.join(&archive)).unwrap());
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    fs::write(release.join("SHA256SUMS"), format!("{hex}  {archive}\n")).unwrap();
+    release
+}
+
+#[allow(dead_code)]
+pub fn release_archive_name(version: &str) -> String {
+    let target = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        (os, arch) => panic!("no release target for {os}-{arch}"),
+    };
+    let extension = if cfg!(windows) { "zip" } else { "tar.gz" };
+    format!("ftime-{version}-{target}.{extension}")
+}
+
+/// Sign `data` the way `minisign -S` does (pre-hashed Ed25519) with a fixed
+/// test key; returns the public key line and the `.minisig` contents.
+#[allow(dead_code)]
+pub fn minisign(data: &[u8]) -> (String, String) {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use blake2::{Blake2b512, Digest};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let key_id = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    let mut public = b"Ed".to_vec();
+    public.extend(key_id);
+    public.extend(key.verifying_key().as_bytes());
+
+    let signature = key.sign(&Blake2b512::digest(data)).to_bytes();
+    let trusted_comment = "timestamp:0\tfile:SHA256SUMS";
+    let mut global = signature.to_vec();
+    global.extend(trusted_comment.as_bytes());
+
+    let mut signature_line = b"ED".to_vec();
+    signature_line.extend(key_id);
+    signature_line.extend(signature);
+    let file = format!(
+        "untrusted comment: signature from test key\n{}\ntrusted comment: {trusted_comment}\n{}\n",
+        STANDARD.encode(signature_line),
+        STANDARD.encode(key.sign(&global).to_bytes())
+    );
+    (STANDARD.encode(public), file)
+}